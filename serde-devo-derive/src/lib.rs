@@ -1,123 +1,309 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
-    parse_macro_input, spanned::Spanned, Attribute, Data, DataEnum, DataStruct, DataUnion,
-    DeriveInput, Field, Ident, Meta, Type, Variant,
+    ext::IdentExt, parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned,
+    Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Expr, ExprLit, Field, Fields,
+    GenericArgument, GenericParam, Ident, Lit, Meta, PathArguments, Token, Type, Variant,
 };
 
 #[proc_macro_derive(Devolve, attributes(devo))]
 pub fn devolve_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    let (vis, name, attrs) = (&ast.vis, &ast.ident, &ast.attrs);
-    let (devo_name, devo_attr) = (format_ident!("Devolved{}", name), format_ident!("devo"));
-
-    let mut serde_attrs = TokenStream::new();
-    let warnings_mod = format_ident!("devolved_{}_warnings", name.to_string().to_lowercase());
-    let devo_fallback_type = attrs.iter().find_map(|attr| match &attr.meta {
-        Meta::List(list) if list.path.get_ident() == Some(&format_ident!("serde")) => {
-            serde_attrs.append_all(quote! { #attr });
-            None
-        }
-        Meta::List(list) if list.path.get_ident() == Some(&devo_attr) => {
-            let mut ft = None;
-            let _ = list.parse_nested_meta(|meta| {
-                let Ok(ty) = meta.value().unwrap().parse::<Type>() else {
-                    return Ok(());
-                };
-                ft = Some(ty);
+    let options = match DevoOptions::parse(&ast.attrs, &format_ident!("devo"), Position::Container)
+    {
+        Ok(options) => options,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    // serde applies the `deny_unknown_fields` of an enum to every struct variant, so it
+    // cannot be lifted for only those variants which capture unknown fields.
+    if let Data::Enum(DataEnum { variants, .. }) = &ast.data {
+        let capturing = variants.iter().find(|v| {
+            DevoOptions::parse(&v.attrs, &format_ident!("devo"), Position::Variant)
+                .is_ok_and(|options| options.capture_unknown_fields)
+        });
+        if let Some(variant) =
+            capturing.filter(|_| SerdeOptions::parse(&ast.attrs).deny_unknown_fields)
+        {
+            return syn::Error::new(
+                variant.ident.span(),
+                "#[devo(capture_unknown_fields)] cannot be used on a variant of an enum with #[serde(deny_unknown_fields)], which serde applies to every variant",
+            )
+            .into_compile_error()
+            .into();
+        }
+    }
+    if options.fallbacks.is_empty() {
+        return render_devolved(ast, None, &[], true).into();
+    }
+    if let Some(param) = options.generic_fallback {
+        return syn::Error::new(
+            param.span(),
+            "#[devo(generic_fallback)] cannot be combined with a list of fallback types",
+        )
+        .into_compile_error()
+        .into();
+    }
 
-                Ok(())
-            });
+    // Each fallback type of a list gets its own devolved type, named after the type, with
+    // the diagnostics of the container reported only once. Each may be mapped from any other.
+    let fallbacks = match options
+        .fallbacks
+        .into_iter()
+        .map(|ty| fallback_suffix(&ty).map(|suffix| (suffix, ty)))
+        .collect::<syn::Result<Vec<_>>>()
+    {
+        Ok(fallbacks) => fallbacks,
+        Err(e) => return e.into_compile_error().into(),
+    };
 
-            ft
-        }
-        _ => None,
-    });
+    fallbacks
+        .iter()
+        .enumerate()
+        .map(|(i, fallback)| {
+            render_devolved(ast.clone(), Some(fallback.clone()), &fallbacks, i == 0)
+        })
+        .collect::<TokenStream>()
+        .into()
+}
 
-    #[cfg(feature = "json")]
-    let fallback_type =
-        { devo_fallback_type.unwrap_or(syn::parse2(quote!(serde_json::Value)).unwrap()) };
+/// The name which distinguishes the devolved type for one of a list of fallback types:
+/// the segments of its path in PascalCase, without a final `Value` segment.
+fn fallback_suffix(ty: &Type) -> syn::Result<String> {
+    let Type::Path(path) = ty else {
+        return Err(syn::Error::new(
+            ty.span(),
+            "each fallback type of a list must be a path, such as `serde_json::Value`",
+        ));
+    };
+    let segments = path.path.segments.iter().collect::<Vec<_>>();
+    let segments = match segments.split_last() {
+        Some((last, rest)) if last.ident == "Value" && !rest.is_empty() => rest,
+        _ => &segments[..],
+    };
 
-    #[cfg(not(feature = "json"))]
-    let fallback_type = {
-        use proc_macro2::Span;
-        if let Some(ty) = devo_fallback_type {
-            ty
-        } else {
-            let warning = syn::Error::new(
-                Span::call_site(),
-                "either enable the \"json\" feature or provide the `#[devo(fallback = Type)]` container attribute",
+    Ok(segments
+        .iter()
+        .map(|segment| {
+            rename(
+                &segment.ident.unraw().to_string(),
+                Some("PascalCase"),
+                false,
             )
-            .into_compile_error();
-            return quote! {
-                mod #warnings_mod {
-                    #warning
+        })
+        .collect())
+}
+
+/// Renders the devolved type of a container and its conversions, for the given fallback
+/// type and the suffix of its name if it is one of a list, which are mapped into from the
+/// devolved types of every type of the list. Diagnostics which concern the container rather
+/// than its fallback type are left out unless `report` is set.
+fn render_devolved(
+    ast: DeriveInput,
+    fallback: Option<(String, Type)>,
+    listed: &[(String, Type)],
+    report: bool,
+) -> TokenStream {
+    let (vis, name, attrs) = (&ast.vis, &ast.ident, &ast.attrs);
+    let (suffix, listed_fallback) = fallback.unzip();
+    let suffix = suffix.unwrap_or_default();
+    let (devo_name, devo_attr) = (
+        format_ident!("Devolved{}{}", name, suffix),
+        format_ident!("devo"),
+    );
+    let mod_prefix = format!("{name}{suffix}").to_lowercase();
+
+    let warnings_mod = format_ident!("devolved_{}_warnings", mod_prefix);
+    let DevoOptions {
+        fallback: devo_fallback_type,
+        derives: devo_derives,
+        capture_unknown_fields,
+        fallback_variant,
+        malformed_variant,
+        unknown,
+        generic_fallback,
+        ..
+    } = match DevoOptions::parse(attrs, &devo_attr, Position::Container) {
+        Ok(options) => options,
+        Err(e) => return e.into_compile_error(),
+    };
+    let devo_fallback_type = listed_fallback.or(devo_fallback_type);
+    let serde_options = SerdeOptions::parse(attrs);
+    // The bounds of a devolved type with a generic fallback are merged into one attribute.
+    let stripped = [
+        ("deny_unknown_fields", capture_unknown_fields),
+        ("bound", generic_fallback.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(name, strip)| strip.then_some(name))
+    .collect::<Vec<_>>();
+    let serde_attrs = attrs
+        .iter()
+        .filter(|attr| attr.path().get_ident() == Some(&format_ident!("serde")))
+        .filter_map(|attr| {
+            if stripped.is_empty() {
+                Some(attr.to_token_stream())
+            } else {
+                without_metas(attr, &stripped)
+            }
+        })
+        .collect::<TokenStream>();
+
+    // A generic fallback is an additional type parameter of the devolved type, of the
+    // conversions between it and the original type, and of the functions which
+    // (de)serialize its fallback variants. Its serde bounds are given explicitly, as serde
+    // would infer one for the parameter which is ambiguous alongside the `Fallback` bound.
+    let mut devo_generics = ast.generics.clone();
+    let is_generic = generic_fallback.is_some();
+    let (fallback_type, fallback_param, serde_bound): (Type, _, _) =
+        match (generic_fallback, devo_fallback_type) {
+            (Some(param), None) => {
+                if ast.generics.type_params().any(|p| p.ident == param) {
+                    return syn::Error::new(
+                        param.span(),
+                        format!("the type already has a generic parameter named `{param}`"),
+                    )
+                    .into_compile_error();
                 }
+                devo_generics.params.push(parse_quote!(#param));
+                devo_generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote!(#param: ::serde_devo::Fallback));
+                let (serialize, deserialize) = generic_fallback_bounds(&ast, &param);
+                (
+                    parse_quote!(#param),
+                    quote!(, #param: ::serde_devo::Fallback),
+                    quote!(#[serde(bound(serialize = #serialize, deserialize = #deserialize))]),
+                )
             }
-            .into();
-        }
+            (Some(param), Some(_)) => {
+                return syn::Error::new(
+                    param.span(),
+                    "#[devo(generic_fallback)] cannot be combined with #[devo(fallback = Type)]",
+                )
+                .into_compile_error();
+            }
+            (None, fallback_type) => (
+                fallback_type.unwrap_or_else(default_fallback_type),
+                TokenStream::new(),
+                TokenStream::new(),
+            ),
+        };
+
+    // A fallback type named by the attribute must implement `Fallback`, which is asserted
+    // where it is named, or required where it is used if it depends on the container's type
+    // parameters.
+    let (require_fallback, fallback_bound) = if is_generic {
+        (TokenStream::new(), TokenStream::new())
+    } else if ast
+        .generics
+        .type_params()
+        .any(|p| mentions(fallback_type.to_token_stream(), &p.ident))
+    {
+        (
+            TokenStream::new(),
+            quote!(where #fallback_type: ::serde_devo::Fallback,),
+        )
+    } else {
+        (
+            quote_spanned! {fallback_type.span()=>
+                const _: fn() = ::serde_devo::__private::require_fallback::<#fallback_type>;
+            },
+            TokenStream::new(),
+        )
     };
 
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
-    let (devo_token, (is_tuple_struct, warn, devo_body, evo_impl, devo_impl)): (
-        TokenStream,
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let (devo_impl_generics, devo_ty_generics, where_clause) = devo_generics.split_for_impl();
+    let variants_mod = format_ident!("devolved_{}_variants", mod_prefix);
+    let mut support = TokenStream::new();
+    let (
+        devo_token,
         (
-            bool,
-            Vec<TokenStream>,
-            TokenStream,
-            TokenStream,
-            TokenStream,
+            is_tuple_struct,
+            warn,
+            devo_body,
+            evo_impl,
+            devo_impl,
+            restore_impl,
+            collect_impl,
+            map_impl,
+            devolved,
         ),
-    ) = match ast.data {
+    ): (TokenStream, RenderedData) = match ast.data {
         Data::Struct(DataStruct {
             fields,
             struct_token,
             ..
         }) => (struct_token.into_token_stream(), {
-            let (is_devo, is_named, tokens, evo_impl, devo_impl): (
-                bool,
-                bool,
-                TokenStream,
-                TokenStream,
-                TokenStream,
-            ) = fields.into_iter().enumerate().fold(
-                (
-                    false,
-                    false,
-                    TokenStream::new(),
-                    TokenStream::new(),
-                    TokenStream::new(),
-                ),
-                |(d, b, mut st, mut evo, mut dvo), (i, f)| {
+            let name_str = name.to_string();
+            let is_newtype = matches!(fields, Fields::Unnamed(_)) && fields.len() == 1;
+            let (is_named, rendered) = fields.into_iter().enumerate().fold(
+                (false, RenderedField::default()),
+                |(b, mut rendered), (i, f)| {
                     let is_named = f.ident.is_some();
-                    let (is_devo, tokens, ev, dv) = if is_named {
-                        render_field(
-                            name.to_string().to_token_stream(),
-                            f,
-                            &devo_attr,
-                            false,
-                            &fallback_type,
-                        )
+                    let segment = field_segment(
+                        &f,
+                        i,
+                        serde_options.rename_all.as_deref(),
+                        serde_options.transparent || is_newtype,
+                    );
+                    let extend = quote!(.extend(#name_str, #segment));
+                    rendered.append(if is_named {
+                        render_field(extend, f, &devo_attr, false, &fallback_type)
                     } else {
-                        render_tuple_field(
-                            name.to_string().into_token_stream(),
-                            f,
-                            &devo_attr,
-                            i,
-                            None,
-                            &fallback_type,
-                        )
-                    };
-                    st.append_all(tokens);
-                    evo.append_all(ev);
-                    dvo.append_all(dv);
-                    (is_devo || d, is_named || b, st, evo, dvo)
+                        render_tuple_field(extend, f, &devo_attr, i, None, &fallback_type)
+                    });
+                    (is_named || b, rendered)
                 },
             );
+            let RenderedField {
+                is_devo,
+                tokens,
+                mut warn,
+                evolve: evo_impl,
+                devolve: devo_impl,
+                restore: restore_impl,
+                collect: collect_impl,
+                map: map_impl,
+                devolved,
+            } = rendered;
 
             let span = name.span();
-            let mut warn = vec![];
-            if !is_devo {
+            let (tokens, devo_impl, restore_impl, map_impl) = if capture_unknown_fields && is_named
+            {
+                (
+                    quote! {
+                        #tokens
+                        #[serde(flatten)]
+                        #vis unknown_fields: ::serde_devo::UnknownFields<#fallback_type>,
+                    },
+                    quote! {
+                        #devo_impl
+                        unknown_fields: ::std::default::Default::default(),
+                    },
+                    quote! {
+                        #restore_impl
+                        self.unknown_fields = previous.unknown_fields;
+                    },
+                    quote! {
+                        #map_impl
+                        unknown_fields: ::serde_devo::MapFallback::<_, #fallback_type>::map_fallback(self.unknown_fields, __map)
+                            .map_err(|e| e.extend(#name_str, ::std::option::Option::None::<::serde_devo::Segment>))?,
+                    },
+                )
+            } else {
+                (tokens, devo_impl, restore_impl, map_impl)
+            };
+            if capture_unknown_fields && !is_named {
+                warn.push(
+                    syn::Error::new(
+                        span,
+                        "#[devo(capture_unknown_fields)] requires a struct with named fields",
+                    )
+                    .into_compile_error(),
+                );
+            } else if !is_devo && !capture_unknown_fields {
                 warn.push(
                         syn::Error::new(
                             span,
@@ -169,6 +355,25 @@ pub fn devolve_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                         )
                     }
                 },
+                quote! {
+                    let _ = &previous;
+                    #restore_impl
+                },
+                if collect_impl.is_empty() {
+                    quote!(::std::vec::Vec::new())
+                } else {
+                    quote! {
+                        let mut __errors = ::std::vec::Vec::new();
+                        #collect_impl
+                        __errors
+                    }
+                },
+                if is_named {
+                    quote!(Ok(#devo_name { #map_impl }))
+                } else {
+                    quote!(Ok(#devo_name ( #map_impl )))
+                },
+                devolved,
             )
         }),
 
@@ -176,118 +381,711 @@ pub fn devolve_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             variants,
             enum_token,
             ..
-        }) => (enum_token.into_token_stream(), {
-            let (is_untagged, tokens, warn, evo_impl, devo_impl): (
-                bool,
-                TokenStream,
-                Vec<TokenStream>,
-                TokenStream,
-                TokenStream,
-            ) = variants.into_iter().fold(
+        }) => {
+            (enum_token.into_token_stream(), {
+                // Content of an untagged enum carries no tag, so none of it is malformed.
+                let tags = variants
+                    .iter()
+                    .filter(|_| !serde_options.untagged)
+                    .map(|v| (&v.ident, SerdeOptions::parse(&v.attrs)))
+                    .filter(|(_, options)| !options.untagged)
+                    .flat_map(|(ident, options)| {
+                        let name = variant_name(ident, &options, &serde_options);
+                        [name].into_iter().chain(options.aliases)
+                    })
+                    .collect::<Vec<_>>();
+                let FallbackVariant {
+                    name: unrecognized_name,
+                    field: unrecognized_field,
+                } = fallback_variant.unwrap_or_else(|| FallbackVariant {
+                    name: format_ident!("UnrecognizedVariant"),
+                    field: None,
+                });
+                let malformed_name =
+                    malformed_variant.unwrap_or_else(|| format_ident!("MalformedVariant"));
+                // The fallback variants are only added without a catch-all, so only then can
+                // they clash with the enum's own.
+                let mut clashes = variants
+                    .iter()
+                    .map(|v| v.ident.clone())
+                    .chain([malformed_name.clone()])
+                    .filter(|ident| *ident == unrecognized_name)
+                    .map(|_| {
+                        syn::Error::new(
+                            unrecognized_name.span(),
+                            format!("the enum already has a variant named `{unrecognized_name}`"),
+                        )
+                        .into_compile_error()
+                    })
+                    .collect::<Vec<_>>();
+                if serde_options.tag.is_some()
+                    && unrecognized_field
+                        .as_ref()
+                        .is_some_and(|field| field == "tag")
+                {
+                    clashes.push(
+                        syn::Error::new(
+                            unrecognized_name.span(),
+                            "the fallback variant of a tagged enum already has a field named `tag`",
+                        )
+                        .into_compile_error(),
+                    );
+                }
+                clashes.extend(
+                    variants
+                        .iter()
+                        .filter(|v| v.ident == malformed_name)
+                        .map(|v| {
+                            syn::Error::new(
+                                v.ident.span(),
+                                format!(
+                                    "the enum already has a variant named `{malformed_name}`, \
+                                     rename the devolved enum's variant for malformed content \
+                                     with `#[devo(malformed_variant = Name)]`"
+                                ),
+                            )
+                            .into_compile_error()
+                        }),
+                );
+                let mut invalid = vec![];
+                if let Some(Unknown::Variant(variant)) = &unknown {
+                    let message = match variants.iter().find(|v| v.ident == *variant) {
+                        Some(v) if v.fields.is_empty() => None,
+                        Some(_) => Some(format!(
+                        "`{variant}` has fields, use `#[devo(unknown(with = ...))]` to construct it"
+                    )),
+                        None => Some(format!("the enum has no variant named `{variant}`")),
+                    };
+                    invalid.extend(message.map(|message| {
+                        syn::Error::new(variant.span(), message).into_compile_error()
+                    }));
+                }
+                let (
+                    has_catch_all,
+                    tokens,
+                    warn,
+                    evo_impl,
+                    devo_impl,
+                    restore_impl,
+                    collect_impl,
+                    map_impl,
+                    devolved,
+                ) = variants.into_iter().fold(
+                    (
+                        false,
+                        TokenStream::new(),
+                        vec![],
+                        TokenStream::new(),
+                        TokenStream::new(),
+                        TokenStream::new(),
+                        TokenStream::new(),
+                        TokenStream::new(),
+                        vec![],
+                    ),
+                    |(
+                        has_catch_all,
+                        mut st,
+                        mut w,
+                        mut evo,
+                        mut dvo,
+                        mut rst,
+                        mut vld,
+                        mut map,
+                        mut dev,
+                    ),
+                     variant| {
+                        let (b, rendered) = render_variant(
+                            name,
+                            &devo_name,
+                            variant,
+                            &devo_attr,
+                            &serde_options,
+                            capture_unknown_fields,
+                            &fallback_type,
+                        );
+                        w.extend(rendered.warn);
+                        st.append_all(rendered.tokens);
+                        evo.append_all(rendered.evolve);
+                        dvo.append_all(rendered.devolve);
+                        rst.append_all(rendered.restore);
+                        vld.append_all(rendered.collect);
+                        map.append_all(rendered.map);
+                        dev.extend(rendered.devolved);
+                        (b || has_catch_all, st, w, evo, dvo, rst, vld, map, dev)
+                    },
+                );
+                match &unknown {
+                    Some(unknown) if has_catch_all => {
+                        let span = match unknown {
+                            Unknown::Variant(variant) => variant.span(),
+                            Unknown::With(constructor) => constructor.span(),
+                        };
+                        invalid.push(
+                            syn::Error::new(
+                                span,
+                                "#[devo(unknown)] cannot be combined with a #[devo(catch_all)] \
+                                 variant, which receives all unknown content",
+                            )
+                            .into_compile_error(),
+                        );
+                    }
+                    _ if !has_catch_all => invalid.extend(clashes),
+                    _ => {}
+                }
+                if !invalid.is_empty() {
+                    return match report {
+                        true => quote!(#(#invalid)*),
+                        false => TokenStream::new(),
+                    };
+                }
+                let name_str = name.to_string();
+                let tag_key = match &serde_options.tag {
+                    Some(tag) => quote!(::std::option::Option::Some(#tag)),
+                    None => quote!(::std::option::Option::None),
+                };
+                // Internally and adjacently tagged variants are split into their tag and the
+                // rest of their content, while any other representation is kept whole.
+                let content_field = unrecognized_field
+                    .clone()
+                    .unwrap_or_else(|| format_ident!("content"));
+                let unmapped = quote!(.map_err(|e| e.extend(#name_str, ::std::option::Option::None::<::serde_devo::Segment>))?);
+                let (
+                    unrecognized_fields,
+                    unrecognized_pattern,
+                    unrecognized_tag,
+                    unrecognized_mod,
+                    unrecognized_map,
+                ) = match (&serde_options.tag, &serde_options.content) {
+                    (Some(tag_key), Some(content_key)) => (
+                        quote! {
+                            {
+                                tag: String,
+                                #content_field: ::std::option::Option<#fallback_type>,
+                                unknown_fields: ::serde_devo::UnknownFields<#fallback_type>,
+                            }
+                        },
+                        quote!({ tag, #content_field: content, unknown_fields }),
+                        quote!(::std::option::Option::Some(tag.clone())),
+                        quote! {
+                            pub(super) fn deserialize_unrecognized<'de, D: ::serde::Deserializer<'de> #fallback_param>(
+                                deserializer: D,
+                            ) -> Result<
+                                (
+                                    String,
+                                    ::std::option::Option<#fallback_type>,
+                                    ::serde_devo::UnknownFields<#fallback_type>,
+                                ),
+                                D::Error,
+                            > {
+                                ::serde_devo::__private::deserialize_adjacent(
+                                    deserializer,
+                                    #tag_key,
+                                    #content_key,
+                                )
+                            }
+
+                            pub(super) fn serialize_unrecognized<S: ::serde::Serializer #fallback_param>(
+                                tag: &str,
+                                content: &::std::option::Option<#fallback_type>,
+                                unknown_fields: &::serde_devo::UnknownFields<#fallback_type>,
+                                serializer: S,
+                            ) -> Result<S::Ok, S::Error> {
+                                ::serde_devo::__private::serialize_adjacent(
+                                    tag,
+                                    content.as_ref(),
+                                    unknown_fields,
+                                    #tag_key,
+                                    #content_key,
+                                    serializer,
+                                )
+                            }
+                        },
+                        quote! {
+                            {
+                                tag,
+                                #content_field: content
+                                    .map(&mut *__map)
+                                    .transpose()
+                                    .map_err(|e| e.extend(#name_str, ::serde_devo::Segment::field(#content_key)))?,
+                                unknown_fields: ::serde_devo::MapFallback::<_, #fallback_type>::map_fallback(unknown_fields, __map) #unmapped,
+                            }
+                        },
+                    ),
+                    (Some(tag_key), None) => (
+                        quote!({ tag: String, #content_field: ::serde_devo::UnknownFields<#fallback_type> }),
+                        quote!({ tag, #content_field: content }),
+                        quote!(::std::option::Option::Some(tag.clone())),
+                        quote! {
+                            pub(super) fn deserialize_unrecognized<'de, D: ::serde::Deserializer<'de> #fallback_param>(
+                                deserializer: D,
+                            ) -> Result<(String, ::serde_devo::UnknownFields<#fallback_type>), D::Error> {
+                                ::serde_devo::__private::deserialize_internal(deserializer, #tag_key)
+                            }
+
+                            pub(super) fn serialize_unrecognized<S: ::serde::Serializer #fallback_param>(
+                                tag: &str,
+                                content: &::serde_devo::UnknownFields<#fallback_type>,
+                                serializer: S,
+                            ) -> Result<S::Ok, S::Error> {
+                                ::serde_devo::__private::serialize_internal(
+                                    tag,
+                                    content,
+                                    #tag_key,
+                                    serializer,
+                                )
+                            }
+                        },
+                        quote! {
+                            {
+                                tag,
+                                #content_field: ::serde_devo::MapFallback::<_, #fallback_type>::map_fallback(content, __map) #unmapped,
+                            }
+                        },
+                    ),
+                    _ => match &unrecognized_field {
+                        // A struct-like variant must still hold the whole of the content, rather
+                        // than expecting a map with an entry named after its field.
+                        Some(field) => (
+                            quote!({ #field: #fallback_type }),
+                            quote!({ #field: content }),
+                            quote!(::serde_devo::__private::find_tag(&content, #tag_key)),
+                            quote! {
+                                pub(super) fn deserialize_unrecognized<'de, D: ::serde::Deserializer<'de> #fallback_param>(
+                                    deserializer: D,
+                                ) -> Result<#fallback_type, D::Error> {
+                                    ::serde::Deserialize::deserialize(deserializer)
+                                }
+
+                                pub(super) fn serialize_unrecognized<S: ::serde::Serializer #fallback_param>(
+                                    content: &#fallback_type,
+                                    serializer: S,
+                                ) -> Result<S::Ok, S::Error> {
+                                    ::serde::Serialize::serialize(content, serializer)
+                                }
+                            },
+                            quote!({ #field: __map(content) #unmapped }),
+                        ),
+                        None => (
+                            quote!((#fallback_type)),
+                            quote!((content)),
+                            quote!(::serde_devo::__private::find_tag(&content, #tag_key)),
+                            TokenStream::new(),
+                            quote!((__map(content) #unmapped)),
+                        ),
+                    },
+                };
+                let (unrecognized_evolve, unrecognized_collect) = match &unknown {
+                    None => (
+                        quote! {
+                            Self::#unrecognized_name #unrecognized_pattern => {
+                                let tag = #unrecognized_tag;
+                                Err(::serde_devo::Error::unknown_variant(#name_str, tag, &content))
+                            }
+                        },
+                        quote! {
+                            Self::#unrecognized_name #unrecognized_pattern => {
+                                let tag = #unrecognized_tag;
+                                __errors.push(::serde_devo::Error::unknown_variant(#name_str, tag, content));
+                            }
+                        },
+                    ),
+                    Some(Unknown::Variant(variant)) => (
+                        quote!(Self::#unrecognized_name { .. } => Ok(#name::#variant),),
+                        quote!(Self::#unrecognized_name { .. } => {}),
+                    ),
+                    Some(Unknown::With(constructor)) => (
+                        quote! {
+                            Self::#unrecognized_name #unrecognized_pattern => {
+                                let tag = #unrecognized_tag;
+                                Ok(#constructor(tag, content))
+                            }
+                        },
+                        quote!(Self::#unrecognized_name { .. } => {}),
+                    ),
+                };
+                // A variant which unknown content evolved into is devolved as itself, so the
+                // content is put back unless the evolved value was changed since.
+                let unrecognized_restore = match &unknown {
+                    Some(Unknown::Variant(variant)) if !has_catch_all => quote! {
+                        if let (Self::#variant, Self::#unrecognized_name { .. }) = (&*self, &previous) {
+                            *self = previous;
+                            return;
+                        }
+                    },
+                    _ => TokenStream::new(),
+                };
+                let (unrecognized, unrecognized_collect, unrecognized_map) = if has_catch_all {
+                    (TokenStream::new(), TokenStream::new(), TokenStream::new())
+                } else {
+                    (
+                        quote! {
+                            Self::#malformed_name { tag, content } => {
+                                Err(::serde_devo::Error::malformed_variant(#name_str, tag, &content))
+                            }
+                            #unrecognized_evolve
+                        },
+                        quote! {
+                            Self::#malformed_name { tag, content } => {
+                                __errors.push(::serde_devo::Error::malformed_variant(#name_str, tag.clone(), content));
+                            }
+                            #unrecognized_collect
+                        },
+                        quote! {
+                            Self::#malformed_name { tag, content } => #devo_name::#malformed_name {
+                                tag,
+                                content: __map(content) #unmapped,
+                            },
+                            Self::#unrecognized_name #unrecognized_pattern => {
+                                #devo_name::#unrecognized_name #unrecognized_map
+                            }
+                        },
+                    )
+                };
+
                 (
                     false,
-                    TokenStream::new(),
-                    vec![],
-                    TokenStream::new(),
-                    TokenStream::new(),
-                ),
-                |(is_untagged, mut st, mut w, mut evo, mut dvo), variant| {
-                    let (b, tokens, warn, ev, dv) =
-                        render_variant(name, &devo_name, variant, &devo_attr, &fallback_type);
-                    w.extend(warn);
-                    st.append_all(tokens);
-                    evo.append_all(dv);
-                    dvo.append_all(ev);
-                    (b || is_untagged, st, w, evo, dvo)
-                },
-            );
+                    warn,
+                    if has_catch_all {
+                        quote! {
+                            {
+                                #tokens
+                            }
+                        }
+                    } else {
+                        let tag_accessor = if serde_options.tag.is_some() {
+                            quote! {
+                                /// The variant tag sent by the peer, if this is an unrecognized variant.
+                                #vis fn unrecognized_tag(&self) -> Option<&str> {
+                                    match self {
+                                        Self::#unrecognized_name { tag, .. } => Some(tag),
+                                        _ => None,
+                                    }
+                                }
+                            }
+                        } else {
+                            let variant_tag = quote_spanned! {fallback_type.span()=>
+                                <#fallback_type as ::serde_devo::Fallback>::variant_tag
+                            };
+                            quote! {
+                                /// The variant tag sent by the peer, if this is an unrecognized variant.
+                                #vis fn unrecognized_tag(&self) -> Option<&str>
+                                #fallback_bound
+                                {
+                                    match self {
+                                        Self::#unrecognized_name #unrecognized_pattern => {
+                                            #variant_tag(content, #tag_key)
+                                        }
+                                        _ => None,
+                                    }
+                                }
+                            }
+                        };
+                        support = quote! {
+                            impl #devo_impl_generics #devo_name #devo_ty_generics #where_clause {
+                                #tag_accessor
+                            }
 
-            (
-                false,
-                warn,
-                if is_untagged {
+                            mod #variants_mod {
+                                use super::*;
+
+                                pub(super) fn deserialize_malformed<'de, D: ::serde::Deserializer<'de> #fallback_param>(
+                                    deserializer: D,
+                                ) -> Result<(String, #fallback_type), D::Error> {
+                                    ::serde_devo::__private::deserialize_malformed(
+                                        deserializer,
+                                        &[#(#tags),*],
+                                        #tag_key,
+                                    )
+                                }
+
+                                #[allow(clippy::ptr_arg)]
+                                pub(super) fn serialize_malformed<S: ::serde::Serializer #fallback_param>(
+                                    _: &String,
+                                    content: &#fallback_type,
+                                    serializer: S,
+                                ) -> Result<S::Ok, S::Error> {
+                                    ::serde::Serialize::serialize(content, serializer)
+                                }
+
+                                #unrecognized_mod
+                            }
+                        };
+                        let (de, ser) = (
+                            format!("{variants_mod}::deserialize_malformed"),
+                            format!("{variants_mod}::serialize_malformed"),
+                        );
+                        let unrecognized_attr = if !unrecognized_mod.is_empty() {
+                            let (de, ser) = (
+                                format!("{variants_mod}::deserialize_unrecognized"),
+                                format!("{variants_mod}::serialize_unrecognized"),
+                            );
+                            quote!(#[serde(untagged, deserialize_with = #de, serialize_with = #ser)])
+                        } else {
+                            quote!(#[serde(untagged)])
+                        };
+                        quote! {
+                            {
+                                #tokens
+                                #[serde(untagged, deserialize_with = #de, serialize_with = #ser)]
+                                #malformed_name { tag: String, content: #fallback_type },
+                                #unrecognized_attr
+                                #unrecognized_name #unrecognized_fields,
+                            }
+                        }
+                    },
                     quote! {
                         {
-                            #tokens
+                            match self {
+                                #evo_impl
+                                #unrecognized
+                            }
                         }
-                    }
-                } else {
+                    },
                     quote! {
                         {
-                            #tokens
-                            #[serde(untagged)]
-                            UnrecognizedVariant(#fallback_type),
-                        }
-                    }
-                },
-                quote! {
-                    {
-                        match self {
-                            #evo_impl
-                            _ => {
-                                let mut e = ::serde_devo::Error::UnknownVariant { ty: "", path: vec![] };
-                                Err(e)
+                            match self {
+                                #devo_impl
                             }
                         }
-                    }
-                },
-                quote! {
-                    {
+                    },
+                    quote! {
+                        #unrecognized_restore
+                        match (self, previous) {
+                            #restore_impl
+                            _ => {}
+                        }
+                    },
+                    quote! {
+                        #[allow(unused_mut)]
+                        let mut __errors = ::std::vec::Vec::new();
                         match self {
-                            #devo_impl
+                            #unrecognized_collect
+                            #collect_impl
                         }
-                    }
-                },
-            )
-        }),
+                        __errors
+                    },
+                    quote! {
+                        Ok(match self {
+                            #map_impl
+                            #unrecognized_map
+                        })
+                    },
+                    devolved,
+                )
+            })
+        }
 
+        Data::Union(_) if !report => return TokenStream::new(),
         Data::Union(DataUnion { fields, .. }) => {
             return quote_spanned! {
                 fields.span() => compile_error!("serde-devolve does not support data unions");
             }
-            .into()
         }
     };
 
+    // Captured fallback content is mapped into this devolved type from the devolved type for
+    // any other fallback type: of any fallback with a generic fallback, of each type of a
+    // list, or otherwise only of the same fallback type. The bounds of generic field types
+    // must be stated for their devolved types to be mapped.
+    let sources: Vec<(Type, TokenStream, syn::Generics)> = if is_generic {
+        let source = format_ident!("__Source");
+        let mut generics = devo_generics.clone();
+        generics.params.push(parse_quote!(#source));
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#source: ::serde_devo::Fallback));
+        let args = ast.generics.params.iter().map(|param| match param {
+            GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+            GenericParam::Type(param) => param.ident.to_token_stream(),
+            GenericParam::Const(param) => param.ident.to_token_stream(),
+        });
+        vec![(
+            parse_quote!(#source),
+            quote!(#devo_name<#(#args,)* #source>),
+            generics,
+        )]
+    } else if listed.is_empty() {
+        vec![(
+            fallback_type.clone(),
+            quote!(#devo_name #devo_ty_generics),
+            devo_generics.clone(),
+        )]
+    } else {
+        listed
+            .iter()
+            .map(|(suffix, ty)| {
+                let source_name = format_ident!("Devolved{}{}", name, suffix);
+                (
+                    ty.clone(),
+                    quote!(#source_name #devo_ty_generics),
+                    devo_generics.clone(),
+                )
+            })
+            .collect()
+    };
+    let is_generic_type = ast.generics.type_params().next().is_some();
+    let map_impls = sources
+        .into_iter()
+        .map(|(source, source_devo, mut generics)| {
+            if is_generic_type {
+                let predicates = &mut generics.make_where_clause().predicates;
+                for ty in &devolved {
+                    predicates.push(parse_quote! {
+                        #ty: ::serde_devo::Devolve<#source> + ::serde_devo::Devolve<#fallback_type>
+                    });
+                    predicates.push(parse_quote! {
+                        <#ty as ::serde_devo::Devolve<#source>>::Devolved: ::serde_devo::MapFallback<
+                            #source,
+                            #fallback_type,
+                            Mapped = <#ty as ::serde_devo::Devolve<#fallback_type>>::Devolved,
+                        >
+                    });
+                }
+            }
+            let (impl_generics, _, where_clause) = generics.split_for_impl();
+            quote! {
+                impl #impl_generics ::serde_devo::MapFallback<#source, #fallback_type> for #source_devo #where_clause {
+                    type Mapped = #devo_name #devo_ty_generics;
+
+                    fn map_fallback<__Map>(
+                        self,
+                        __map: &mut __Map,
+                    ) -> Result<Self::Mapped, ::serde_devo::TranscodeError>
+                    where
+                        __Map: FnMut(#source) -> Result<#fallback_type, ::serde_devo::TranscodeError>,
+                    {
+                        let _ = &__map;
+                        #map_impl
+                    }
+                }
+            }
+        })
+        .collect::<TokenStream>();
+    // A devolved type of a list is mapped into the devolved type of each other type of the
+    // list, so the target of `MapFallback::transcode` called on it cannot be inferred.
+    let map_into = if listed.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            impl #devo_impl_generics #devo_name #devo_ty_generics #where_clause {
+                /// Converts every piece of captured fallback content into `__Target` with
+                /// `serde_devo::transcode`, giving the devolved type of that fallback type.
+                #vis fn transcode_into<__Target>(
+                    self,
+                ) -> Result<
+                    <Self as ::serde_devo::MapFallback<#fallback_type, __Target>>::Mapped,
+                    ::serde_devo::TranscodeError,
+                >
+                where
+                    Self: ::serde_devo::MapFallback<#fallback_type, __Target>,
+                    __Target: ::serde::de::DeserializeOwned,
+                {
+                    ::serde_devo::MapFallback::<#fallback_type, __Target>::transcode(self)
+                }
+
+                /// Converts every piece of captured fallback content into `__Target` with
+                /// `__map`, giving the devolved type of that fallback type.
+                #vis fn map_fallback_into<__Target, __Map>(
+                    self,
+                    __map: &mut __Map,
+                ) -> Result<
+                    <Self as ::serde_devo::MapFallback<#fallback_type, __Target>>::Mapped,
+                    ::serde_devo::TranscodeError,
+                >
+                where
+                    Self: ::serde_devo::MapFallback<#fallback_type, __Target>,
+                    __Map: FnMut(#fallback_type) -> Result<__Target, ::serde_devo::TranscodeError>,
+                {
+                    ::serde_devo::MapFallback::<#fallback_type, __Target>::map_fallback(self, __map)
+                }
+            }
+        }
+    };
+
+    let warn = if report { warn } else { vec![] };
     let d = if is_tuple_struct {
         quote! {
-            #[derive(::serde::Deserialize, ::serde::Serialize)]
+            #[derive(::serde::Deserialize, ::serde::Serialize, #(#devo_derives),*)]
             #serde_attrs
-            #vis #devo_token #devo_name #ty_generics #devo_body #where_clause;
+            #serde_bound
+            #vis #devo_token #devo_name #devo_ty_generics #devo_body #where_clause;
         }
     } else {
+        // The names of the variants appended to a devolved enum are not the user's to choose,
+        // so its variant names are not linted.
         quote! {
-            #[derive(::serde::Deserialize, ::serde::Serialize)]
+            #[derive(::serde::Deserialize, ::serde::Serialize, #(#devo_derives),*)]
+            #[allow(clippy::enum_variant_names)]
             #serde_attrs
-            #vis #devo_token #devo_name #ty_generics #where_clause #devo_body
+            #serde_bound
+            #vis #devo_token #devo_name #devo_ty_generics #where_clause #devo_body
         }
     };
     quote! {
         #d
 
-        impl #impl_generics ::serde_devo::Devolve<#fallback_type> for #name #ty_generics #where_clause {
-            type Devolved = #devo_name #ty_generics;
+        impl #devo_impl_generics ::serde_devo::Devolve<#fallback_type> for #name #ty_generics #where_clause {
+            type Devolved = #devo_name #devo_ty_generics;
 
             fn into_devolved(self) -> Self::Devolved {
                 #devo_impl
             }
         }
 
-        impl #impl_generics ::serde_devo::Evolve<#fallback_type> for #devo_name #ty_generics #where_clause {
+        impl #devo_impl_generics ::serde_devo::Evolve<#fallback_type> for #devo_name #devo_ty_generics #where_clause {
             type Evolved = #name #ty_generics;
 
             fn try_into_evolved(self) -> Result<Self::Evolved, ::serde_devo::Error> {
+                self.try_into_evolved_with(&mut ::std::vec::Vec::new())
+            }
+
+            fn try_into_evolved_with(
+                self,
+                __dropped: &mut ::std::vec::Vec<::serde_devo::Error>,
+            ) -> Result<Self::Evolved, ::serde_devo::Error> {
+                let _ = &__dropped;
                 #evo_impl
             }
+
+            fn restore(&mut self, previous: Self) {
+                #restore_impl
+            }
+
+            fn collect_errors(&self) -> Vec<::serde_devo::Error> {
+                #collect_impl
+            }
         }
 
+        #require_fallback
+
+        #map_impls
+
+        #map_into
+
+        #support
+
         mod #warnings_mod {
             #(
                 #warn
             )*
         }
     }
-    .into()
+}
+
+/// Whether `tokens` name the identifier `ident` anywhere within them.
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(i) => i == *ident,
+        proc_macro2::TokenTree::Group(g) => mentions(g.stream(), ident),
+        _ => false,
+    })
+}
+
+/// The fallback type of a container without the `#[devo(fallback = Type)]` attribute, which
+/// is the default of the `Devolve` trait whatever features are enabled.
+fn default_fallback_type() -> Type {
+    parse_quote!(::serde_devo::Value)
 }
 
 fn render_variant(
@@ -300,15 +1098,48 @@ fn render_variant(
         ..
     }: Variant,
     devo_attr: &Ident,
+    serde_options: &SerdeOptions,
+    capture_unknown_fields: bool,
     fallback_type: &Type,
-) -> (
-    bool,
-    TokenStream,
-    Vec<TokenStream>,
-    TokenStream,
-    TokenStream,
-) {
+) -> (bool, RenderedField) {
     let mut warn = vec![];
+    let name_str = evo_name.to_string();
+    let variant_options = SerdeOptions::parse(&attrs);
+    let variant_segment = if serde_options.untagged || variant_options.untagged {
+        quote!(::std::option::Option::None::<::serde_devo::Segment>)
+    } else if let Some(content) = &serde_options.content {
+        quote!(::serde_devo::Segment::field(#content))
+    } else if serde_options.tag.is_some() {
+        quote!(::std::option::Option::None::<::serde_devo::Segment>)
+    } else {
+        let variant = variant_name(&ident, &variant_options, serde_options);
+        quote!(::serde_devo::Segment::variant(#variant))
+    };
+    let field_rename_rule = variant_options
+        .rename_all
+        .as_deref()
+        .or(serde_options.rename_all_fields.as_deref());
+    let is_newtype = matches!(fields, Fields::Unnamed(_)) && fields.len() == 1;
+    let (capture_unknown_fields, is_catch_all) =
+        match DevoOptions::parse(&attrs, devo_attr, Position::Variant) {
+            Ok(options) => (
+                options.capture_unknown_fields || capture_unknown_fields,
+                options.catch_all,
+            ),
+            Err(e) => {
+                warn.push(e.into_compile_error());
+                (capture_unknown_fields, false)
+            }
+        };
+    if is_catch_all && !variant_options.untagged {
+        warn.push(
+            syn::Error::new(
+                ident.span(),
+                "#[devo(catch_all)] requires a #[serde(untagged)] variant",
+            )
+            .into_compile_error(),
+        );
+    }
     let is_empty = fields.is_empty();
     let field_names = fields
         .iter()
@@ -328,42 +1159,73 @@ fn render_variant(
         .map(|n| n.to_string())
         .collect::<Vec<_>>();
     let (is_devo, is_untagged, attrs) = render_attrs(attrs, devo_attr);
-    let (is_named, tokens, e_impl, d_impl): (bool, TokenStream, TokenStream, TokenStream) =
-        fields.into_iter().zip(&field_letters).enumerate().fold(
-            (
-                false,
-                TokenStream::new(),
-                TokenStream::new(),
-                TokenStream::new(),
-            ),
-            |(b, mut st, mut evo, mut dvo), (i, (f, l))| {
-                let is_named = f.ident.is_some();
-                let (_is_devo, tokens, ev, dv) = if is_named {
-                    render_field(
-                        format!("{evo_name}::{ident}").to_token_stream(),
-                        f,
-                        devo_attr,
-                        true,
-                        fallback_type,
-                    )
-                } else {
-                    render_tuple_field(
-                        format!("{evo_name}::{ident}").to_token_stream(),
-                        f,
-                        devo_attr,
-                        i,
-                        Some(l),
-                        fallback_type,
-                    )
-                };
-                st.append_all(tokens);
-                evo.append_all(ev);
-                dvo.append_all(dv);
-                (is_named || b, st, evo, dvo)
-            },
-        );
+    let (mut current_bindings, mut previous_bindings) = (vec![], vec![]);
+    let (is_named, rendered) = fields.into_iter().zip(&field_letters).enumerate().fold(
+        (false, RenderedField::default()),
+        |(b, mut rendered), (i, (f, l))| {
+            let is_named = f.ident.is_some();
+            let binding = f.ident.clone().unwrap_or_else(|| format_ident!("{}", l));
+            let segment = field_segment(&f, i, field_rename_rule, is_newtype);
+            let extend = quote!(.extend(#name_str, #segment).extend(#name_str, #variant_segment));
+            let field = if is_named {
+                render_field(extend, f, devo_attr, true, fallback_type)
+            } else {
+                render_tuple_field(extend, f, devo_attr, i, Some(l), fallback_type)
+            };
+            if field.restore.is_empty() {
+                current_bindings.push(quote!(_));
+                previous_bindings.push(quote!(_));
+            } else {
+                let previous = format_ident!("previous_{}", binding);
+                current_bindings.push(quote!(#binding));
+                previous_bindings.push(quote!(#previous));
+            }
+            rendered.append(field);
+            (is_named || b, rendered)
+        },
+    );
+    let RenderedField {
+        tokens,
+        warn: w,
+        evolve: e_impl,
+        devolve: d_impl,
+        restore: r_impl,
+        collect: v_impl,
+        map: m_impl,
+        devolved,
+        ..
+    } = rendered;
+    warn.extend(w);
+    let field_bindings = current_bindings.clone();
 
     let field_letters = field_letters.join(", ").parse::<TokenStream>().unwrap();
+    let (tokens, d_impl, r_impl, m_bindings, m_impl) = if capture_unknown_fields && is_named {
+        current_bindings.push(quote!(unknown_fields));
+        previous_bindings.push(quote!(previous_unknown_fields));
+        (
+            quote! {
+                #tokens
+                #[serde(flatten)]
+                unknown_fields: ::serde_devo::UnknownFields<#fallback_type>,
+            },
+            quote! {
+                #d_impl
+                unknown_fields: ::std::default::Default::default(),
+            },
+            quote! {
+                #r_impl
+                *unknown_fields = previous_unknown_fields;
+            },
+            quote!(#field_names, unknown_fields),
+            quote! {
+                #m_impl
+                unknown_fields: ::serde_devo::MapFallback::<_, #fallback_type>::map_fallback(unknown_fields, __map)
+                    .map_err(|e| e.extend(#name_str, #variant_segment))?,
+            },
+        )
+    } else {
+        (tokens, d_impl, r_impl, field_names.clone(), m_impl)
+    };
     let tokens = if is_named {
         quote! {
             #attrs
@@ -419,6 +1281,64 @@ fn render_variant(
         }
     };
 
+    let map_impl = if is_empty {
+        quote! {
+            #member => #devo_member,
+        }
+    } else if is_named {
+        quote! {
+            #member { #m_bindings } => #devo_member { #m_impl },
+        }
+    } else {
+        quote! {
+            #member ( #field_letters ) => #devo_member ( #m_impl ),
+        }
+    };
+
+    let collect_impl = {
+        let bindings = field_bindings.iter().filter(|c| c.to_string() != "_");
+        if v_impl.is_empty() {
+            quote! {
+                #member { .. } => {}
+            }
+        } else if is_named {
+            quote! {
+                #member { #(#bindings,)* .. } => {
+                    #v_impl
+                }
+            }
+        } else {
+            quote! {
+                #member ( #(#field_bindings),* ) => {
+                    #v_impl
+                }
+            }
+        }
+    };
+
+    let restore_impl = if r_impl.is_empty() {
+        TokenStream::new()
+    } else if is_named {
+        let names = current_bindings
+            .iter()
+            .zip(&previous_bindings)
+            .filter(|(c, _)| c.to_string() != "_")
+            .map(|(c, p)| quote!(#c: #p))
+            .collect::<Vec<_>>();
+        let current_bindings = current_bindings.iter().filter(|c| c.to_string() != "_");
+        quote! {
+            (#member { #(#current_bindings,)* .. }, #member { #(#names,)* .. }) => {
+                #r_impl
+            }
+        }
+    } else {
+        quote! {
+            (#member ( #(#current_bindings),* ), #member ( #(#previous_bindings),* )) => {
+                #r_impl
+            }
+        }
+    };
+
     let span = ident.span();
     if is_named && is_devo {
         warn.push(
@@ -437,66 +1357,251 @@ fn render_variant(
         );
     }
 
-    (is_untagged, tokens, warn, evo_impl, devo_impl)
+    (
+        is_untagged && is_catch_all,
+        RenderedField {
+            is_devo,
+            tokens,
+            warn,
+            evolve: devo_impl,
+            devolve: evo_impl,
+            restore: restore_impl,
+            collect: collect_impl,
+            map: map_impl,
+            devolved,
+        },
+    )
+}
+
+/// The parts of a devolved type and its conversions contributed by one or more fields.
+#[derive(Default)]
+struct RenderedField {
+    is_devo: bool,
+    tokens: TokenStream,
+    warn: Vec<TokenStream>,
+    evolve: TokenStream,
+    devolve: TokenStream,
+    restore: TokenStream,
+    collect: TokenStream,
+    map: TokenStream,
+    devolved: Vec<Type>,
+}
+
+impl RenderedField {
+    fn append(&mut self, other: RenderedField) {
+        self.is_devo |= other.is_devo;
+        self.tokens.append_all(other.tokens);
+        self.warn.extend(other.warn);
+        self.evolve.append_all(other.evolve);
+        self.devolve.append_all(other.devolve);
+        self.restore.append_all(other.restore);
+        self.collect.append_all(other.collect);
+        self.map.append_all(other.map);
+        self.devolved.extend(other.devolved);
+    }
+}
+
+fn parse_field_options(
+    attrs: &[Attribute],
+    devo_attr: &Ident,
+    warn: &mut Vec<TokenStream>,
+) -> DevoOptions {
+    DevoOptions::parse(attrs, devo_attr, Position::Field).unwrap_or_else(|e| {
+        warn.push(e.into_compile_error());
+        DevoOptions::default()
+    })
+}
+
+/// Evolves and collects the errors of a `#[devo(lenient)]` field, which is devolved as a
+/// `Lenient` of its own type rather than its `Devolved` type.
+fn render_lenient(
+    member: &TokenStream,
+    borrowed: &TokenStream,
+    extend: &TokenStream,
+    on_unknown: Option<OnUnknown>,
+    warn: &mut Vec<TokenStream>,
+) -> (TokenStream, TokenStream) {
+    let fallback = match on_unknown.unwrap_or(OnUnknown::Error) {
+        OnUnknown::Error => {
+            return (
+                quote!(::serde_devo::__private::evolve_lenient(#member).map_err(|e| e #extend)?,),
+                quote! {
+                    __errors.extend(::serde_devo::__private::lenient_error(#borrowed).map(|e| e #extend));
+                },
+            );
+        }
+        OnUnknown::Skip => {
+            warn.push(
+                syn::Error::new(
+                    member.span(),
+                    "#[devo(lenient)] fields support the \"error\", \"none\" and \"default\" policies",
+                )
+                .into_compile_error(),
+            );
+            quote!(::std::default::Default::default())
+        }
+        OnUnknown::None => quote!(::std::option::Option::None),
+        OnUnknown::Default => quote!(::std::default::Default::default()),
+    };
+
+    (
+        quote! {
+            match ::serde_devo::__private::evolve_lenient(#member) {
+                Ok(t) => t,
+                Err(e) => {
+                    __dropped.push(e #extend);
+                    #fallback
+                }
+            },
+        },
+        TokenStream::new(),
+    )
+}
+
+/// Evolves a devolved field according to its `on_unknown` policy, followed by a comma.
+fn render_evolve(
+    ty: &Type,
+    member: &TokenStream,
+    extend: &TokenStream,
+    on_unknown: Option<OnUnknown>,
+    fallback_type: &Type,
+) -> TokenStream {
+    let fallback = match on_unknown.unwrap_or(OnUnknown::Error) {
+        OnUnknown::Error => {
+            return quote! {
+                ::serde_devo::__private::evolve_within::<<#ty as ::serde_devo::Devolve<#fallback_type>>::Devolved, #fallback_type>(#member, __dropped, |e| e #extend)?,
+            }
+        }
+        OnUnknown::Skip => {
+            return quote! {
+                ::serde_devo::__private::evolve_skipping::<_, #ty, #fallback_type>(#member, __dropped, |e| e #extend),
+            }
+        }
+        OnUnknown::None => quote!(::std::option::Option::None),
+        OnUnknown::Default => quote!(::std::default::Default::default()),
+    };
+
+    quote! {
+        ::serde_devo::__private::evolve_or::<_, #fallback_type>(#member, __dropped, || #fallback, |e| e #extend),
+    }
 }
 
 fn render_tuple_field(
-    parent_ty: TokenStream,
+    extend: TokenStream,
     Field { vis, attrs, ty, .. }: Field,
     devo_attr: &Ident,
     i: usize,
     l: Option<&str>,
     fallback_type: &Type,
-) -> (bool, TokenStream, TokenStream, TokenStream) {
+) -> RenderedField {
     let ty = &ty;
+    let mut warn = vec![];
+    let DevoOptions {
+        on_unknown,
+        lenient,
+        ..
+    } = parse_field_options(&attrs, devo_attr, &mut warn);
+    let on_unknown = check_on_unknown(ty, on_unknown).unwrap_or_else(|e| {
+        warn.push(e.into_compile_error());
+        Some(OnUnknown::Error)
+    });
     let (is_devo, _, attrs) = render_attrs(attrs, devo_attr);
-    let member = (if let Some(l) = l {
-        l.to_string()
+    let is_devo = is_devo || on_unknown.is_some();
+    let (member, borrowed, current, previous) = if let Some(l) = l {
+        let (l, p) = (format_ident!("{}", l), format_ident!("previous_{}", l));
+        (quote!(#l), quote!(#l), quote!(#l), quote!(#p))
     } else {
-        format!("self.{}", i)
-    })
-    .parse::<TokenStream>()
-    .unwrap();
-    let idx = i.to_string().to_token_stream();
-    if is_devo {
-        if let Some(id) = match ty {
-            Type::Path(p) => p.path.get_ident(),
-            _ => None,
-        } {
-            let devolved_ident = format_ident!("{}", id);
-            return (
+        let i = syn::Index::from(i);
+        (
+            quote!(self.#i),
+            quote!(&self.#i),
+            quote!(&mut self.#i),
+            quote!(previous.#i),
+        )
+    };
+    if lenient {
+        let (evolve, collect) = render_lenient(&member, &borrowed, &extend, on_unknown, &mut warn);
+        return RenderedField {
+            is_devo: true,
+            tokens: quote! {
+                #attrs
+                #vis ::serde_devo::Lenient<#ty, #fallback_type>,
+            },
+            warn,
+            evolve,
+            devolve: quote! {
+                ::serde_devo::Lenient::Known(#member),
+            },
+            restore: TokenStream::new(),
+            collect,
+            map: quote! {
+                ::serde_devo::__private::map_lenient(#member, __map).map_err(|e| e #extend)?,
+            },
+            devolved: vec![],
+        };
+    }
+    match check_devolvable(ty) {
+        Err(e) if is_devo => warn.push(e.into_compile_error()),
+        Ok(()) if is_devo => {
+            return RenderedField {
                 is_devo,
-                quote! {
+                tokens: quote! {
                     #attrs
-                    #vis <#devolved_ident as ::serde_devo::Devolve<#fallback_type>>::Devolved,
+                    #vis <#ty as ::serde_devo::Devolve<#fallback_type>>::Devolved,
                 },
-                quote! {
-                    <<#devolved_ident as ::serde_devo::Devolve<#fallback_type>>::Devolved as ::serde_devo::Evolve<#fallback_type>>::try_into_evolved(#member).map_err(|e| e.extend(#parent_ty, #idx))?,
+                warn,
+                evolve: render_evolve(ty, &member, &extend, on_unknown, fallback_type),
+                devolve: quote! {
+                    <#ty as ::serde_devo::Devolve<#fallback_type>>::into_devolved(#member),
                 },
-                quote! {
-                    <#devolved_ident as ::serde_devo::Devolve<#fallback_type>>::into_devolved(#member),
+                map: quote! {
+                    ::serde_devo::MapFallback::<_, #fallback_type>::map_fallback(#member, __map).map_err(|e| e #extend)?,
                 },
-            );
+                devolved: vec![ty.clone()],
+                restore: match on_unknown {
+                    Some(OnUnknown::Skip) => quote! {
+                        ::serde_devo::__private::restore_skipping::<_, #fallback_type>(#current, #previous);
+                    },
+                    _ => quote! {
+                        <<#ty as ::serde_devo::Devolve<#fallback_type>>::Devolved as ::serde_devo::Evolve<#fallback_type>>::restore(#current, #previous);
+                    },
+                },
+                // Unknown content in a lossy field does not prevent evolution.
+                collect: match on_unknown {
+                    None | Some(OnUnknown::Error) => quote! {
+                        __errors.extend(<<#ty as ::serde_devo::Devolve<#fallback_type>>::Devolved as ::serde_devo::Evolve<#fallback_type>>::collect_errors(#borrowed).into_iter().map(|e| e #extend));
+                    },
+                    Some(_) => TokenStream::new(),
+                },
+            };
         }
+        _ => {}
     }
 
-    (
+    RenderedField {
         is_devo,
-        quote! {
+        tokens: quote! {
             #attrs
             #vis #ty,
         },
-        quote! {
+        warn,
+        evolve: quote! {
             #member,
         },
-        quote! {
+        devolve: quote! {
             #member,
         },
-    )
+        restore: TokenStream::new(),
+        collect: TokenStream::new(),
+        map: quote! {
+            #member,
+        },
+        devolved: vec![],
+    }
 }
 
 fn render_field(
-    parent_ty: TokenStream,
+    extend: TokenStream,
     Field {
         vis,
         attrs,
@@ -507,54 +1612,576 @@ fn render_field(
     devo_attr: &Ident,
     is_enum: bool,
     fallback_type: &Type,
-) -> (bool, TokenStream, TokenStream, TokenStream) {
+) -> RenderedField {
     let ty = &ty;
+    let mut warn = vec![];
+    let DevoOptions {
+        on_unknown,
+        lenient,
+        ..
+    } = parse_field_options(&attrs, devo_attr, &mut warn);
+    let on_unknown = check_on_unknown(ty, on_unknown).unwrap_or_else(|e| {
+        warn.push(e.into_compile_error());
+        Some(OnUnknown::Error)
+    });
     let (is_devo, _, attrs) = render_attrs(attrs, devo_attr);
+    let is_devo = is_devo || on_unknown.is_some();
     let i = format!("{}", ident.as_ref().unwrap());
-    let member = (if is_enum {
-        i.clone()
+    let (member, borrowed, current, previous) = if is_enum {
+        let p = format_ident!("previous_{}", i);
+        (quote!(#ident), quote!(#ident), quote!(#ident), quote!(#p))
     } else {
-        format!("self.{}", ident.as_ref().unwrap())
-    })
-    .parse::<TokenStream>()
-    .unwrap();
-    if is_devo {
-        if let Some(id) = match ty {
-            Type::Path(p) => p.path.get_ident(),
-            _ => None,
-        } {
-            let devolved_ident = format_ident!("{}", id);
-            return (
+        (
+            quote!(self.#ident),
+            quote!(&self.#ident),
+            quote!(&mut self.#ident),
+            quote!(previous.#ident),
+        )
+    };
+    if lenient {
+        let (evolve, collect) = render_lenient(&member, &borrowed, &extend, on_unknown, &mut warn);
+        return RenderedField {
+            is_devo: true,
+            tokens: quote! {
+                #attrs
+                #vis #ident: ::serde_devo::Lenient<#ty, #fallback_type>,
+            },
+            warn,
+            evolve: quote!(#ident: #evolve),
+            devolve: quote! {
+                #ident: ::serde_devo::Lenient::Known(#member),
+            },
+            restore: TokenStream::new(),
+            collect,
+            map: quote! {
+                #ident: ::serde_devo::__private::map_lenient(#member, __map).map_err(|e| e #extend)?,
+            },
+            devolved: vec![],
+        };
+    }
+    match check_devolvable(ty) {
+        Err(e) if is_devo => warn.push(e.into_compile_error()),
+        Ok(()) if is_devo => {
+            return RenderedField {
                 is_devo,
-                quote! {
+                tokens: quote! {
                     #attrs
-                    #vis #ident: <#devolved_ident as ::serde_devo::Devolve<#fallback_type>>::Devolved,
+                    #vis #ident: <#ty as ::serde_devo::Devolve<#fallback_type>>::Devolved,
                 },
-                quote! {
-                    #ident: <<#devolved_ident as ::serde_devo::Devolve<#fallback_type>>::Devolved as ::serde_devo::Evolve<#fallback_type>>::try_into_evolved(#member).map_err(|e| e.extend(#parent_ty, #i))?,
+                warn,
+                evolve: {
+                    let evolve = render_evolve(ty, &member, &extend, on_unknown, fallback_type);
+                    quote!(#ident: #evolve)
                 },
-                quote! {
-                    #ident: <#devolved_ident as ::serde_devo::Devolve<#fallback_type>>::into_devolved(#member),
+                devolve: quote! {
+                    #ident: <#ty as ::serde_devo::Devolve<#fallback_type>>::into_devolved(#member),
                 },
-            );
+                map: quote! {
+                    #ident: ::serde_devo::MapFallback::<_, #fallback_type>::map_fallback(#member, __map).map_err(|e| e #extend)?,
+                },
+                devolved: vec![ty.clone()],
+                restore: match on_unknown {
+                    Some(OnUnknown::Skip) => quote! {
+                        ::serde_devo::__private::restore_skipping::<_, #fallback_type>(#current, #previous);
+                    },
+                    _ => quote! {
+                        <<#ty as ::serde_devo::Devolve<#fallback_type>>::Devolved as ::serde_devo::Evolve<#fallback_type>>::restore(#current, #previous);
+                    },
+                },
+                // Unknown content in a lossy field does not prevent evolution.
+                collect: match on_unknown {
+                    None | Some(OnUnknown::Error) => quote! {
+                        __errors.extend(<<#ty as ::serde_devo::Devolve<#fallback_type>>::Devolved as ::serde_devo::Evolve<#fallback_type>>::collect_errors(#borrowed).into_iter().map(|e| e #extend));
+                    },
+                    Some(_) => TokenStream::new(),
+                },
+            };
         }
+        _ => {}
     }
 
-    (
+    RenderedField {
         is_devo,
-        quote! {
+        tokens: quote! {
             #attrs
             #vis #ident: #ty,
         },
-        quote! {
+        warn,
+        evolve: quote! {
             #ident: #member,
         },
-        quote! {
+        devolve: quote! {
+            #ident: #member,
+        },
+        restore: TokenStream::new(),
+        collect: TokenStream::new(),
+        map: quote! {
             #ident: #member,
         },
+        devolved: vec![],
+    }
+}
+
+/// The name of a variant in the serialized data.
+fn variant_name(
+    ident: &Ident,
+    variant_options: &SerdeOptions,
+    serde_options: &SerdeOptions,
+) -> String {
+    variant_options.rename.clone().unwrap_or_else(|| {
+        rename(
+            &ident.unraw().to_string(),
+            serde_options.rename_all.as_deref(),
+            true,
+        )
+    })
+}
+
+/// The path segment locating a field within its parent in the serialized data, which is
+/// `None` where the field's content is serialized inline.
+fn field_segment(
+    field: &Field,
+    i: usize,
+    rename_rule: Option<&str>,
+    is_inline: bool,
+) -> TokenStream {
+    let options = SerdeOptions::parse(&field.attrs);
+    match &field.ident {
+        _ if is_inline || options.flatten => {
+            quote!(::std::option::Option::None::<::serde_devo::Segment>)
+        }
+        Some(ident) => {
+            let name = options
+                .rename
+                .unwrap_or_else(|| rename(&ident.unraw().to_string(), rename_rule, false));
+            quote!(::serde_devo::Segment::field(#name))
+        }
+        None => quote!(::serde_devo::Segment::Index(#i)),
+    }
+}
+
+/// Applies a serde `rename_all` rule to a field or variant name, as serde itself would.
+fn rename(name: &str, rule: Option<&str>, is_variant: bool) -> String {
+    let snake = if is_variant {
+        name.chars()
+            .enumerate()
+            .fold(String::new(), |mut snake, (i, c)| {
+                if i > 0 && c.is_uppercase() {
+                    snake.push('_');
+                }
+                snake.push(c.to_ascii_lowercase());
+                snake
+            })
+    } else {
+        name.to_string()
+    };
+    let pascal = if is_variant {
+        name.to_string()
+    } else {
+        name.split('_').fold(String::new(), |mut pascal, word| {
+            let mut chars = word.chars();
+            if let Some(c) = chars.next() {
+                pascal.push(c.to_ascii_uppercase());
+                pascal.extend(chars);
+            }
+            pascal
+        })
+    };
+
+    match rule {
+        Some("lowercase") if is_variant => name.to_ascii_lowercase(),
+        Some("lowercase") | Some("snake_case") => snake,
+        Some("UPPERCASE") => name.to_ascii_uppercase(),
+        Some("PascalCase") => pascal,
+        Some("camelCase") => {
+            let mut chars = pascal.chars();
+            chars.next().map_or_else(String::new, |c| {
+                c.to_ascii_lowercase().to_string() + chars.as_str()
+            })
+        }
+        Some("SCREAMING_SNAKE_CASE") => snake.to_ascii_uppercase(),
+        Some("kebab-case") => snake.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => snake.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+/// The serde attributes of a container, variant or field which determine where its content
+/// appears in the serialized data.
+#[derive(Default)]
+struct SerdeOptions {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    rename_all_fields: Option<String>,
+    tag: Option<String>,
+    content: Option<String>,
+    aliases: Vec<String>,
+    untagged: bool,
+    deny_unknown_fields: bool,
+    flatten: bool,
+    transparent: bool,
+}
+
+impl SerdeOptions {
+    fn parse(attrs: &[Attribute]) -> Self {
+        let metas = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("serde"))
+            .filter_map(|attr| {
+                attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .ok()
+            })
+            .flatten();
+
+        metas.fold(Self::default(), |mut options, meta| {
+            let name = deserialize_name(&meta);
+            match meta.path().get_ident().map(|i| i.to_string()).as_deref() {
+                Some("rename") => options.rename = name.or(options.rename),
+                Some("rename_all") => options.rename_all = name.or(options.rename_all),
+                Some("rename_all_fields") => {
+                    options.rename_all_fields = name.or(options.rename_all_fields)
+                }
+                Some("tag") => options.tag = name.or(options.tag),
+                Some("content") => options.content = name.or(options.content),
+                Some("alias") => options.aliases.extend(name),
+                Some("untagged") => options.untagged = true,
+                Some("deny_unknown_fields") => options.deny_unknown_fields = true,
+                Some("flatten") => options.flatten = true,
+                Some("transparent") => options.transparent = true,
+                _ => {}
+            }
+            options
+        })
+    }
+}
+
+/// The serde bounds of a devolved type with a generic fallback, for serializing and for
+/// deserializing. These are the user's own `#[serde(bound)]`, or else those serde would
+/// infer for each type parameter which a field mentions, along with the `Fallback` bound
+/// of the fallback parameter, which is used in place of the `Deserialize<'de>` bound serde
+/// would infer for it and which would be ambiguous alongside it.
+fn generic_fallback_bounds(ast: &DeriveInput, param: &Ident) -> (String, String) {
+    let fields = match &ast.data {
+        Data::Struct(DataStruct { fields, .. }) => fields.iter().collect::<Vec<_>>(),
+        Data::Enum(DataEnum { variants, .. }) => variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => vec![],
+    };
+    let used = ast
+        .generics
+        .type_params()
+        .map(|p| &p.ident)
+        .filter(|ident| {
+            fields
+                .iter()
+                .any(|f| mentions(f.ty.to_token_stream(), ident))
+        })
+        .collect::<Vec<_>>();
+    let user_bound = |direction: &str| {
+        ast.attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("serde"))
+            .filter_map(|attr| {
+                attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .ok()
+            })
+            .flatten()
+            .filter(|meta| meta.path().is_ident("bound"))
+            .find_map(|meta| match &meta {
+                Meta::NameValue(_) => deserialize_name(&meta),
+                Meta::List(list) => list
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .ok()?
+                    .iter()
+                    .find(|meta| meta.path().is_ident(direction))
+                    .and_then(deserialize_name),
+                Meta::Path(_) => None,
+            })
+    };
+    let bounds = |direction: &str, bound: &str| {
+        let inferred = used.iter().map(|ident| format!("{ident}: {bound}"));
+        user_bound(direction)
+            .map_or_else(|| inferred.collect(), |bound| vec![bound])
+            .into_iter()
+            .filter(|bound| !bound.trim().is_empty())
+            .chain([format!("{param}: ::serde_devo::Fallback")])
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    (
+        bounds("serialize", "::serde::Serialize"),
+        bounds("deserialize", "::serde::Deserialize<'de>"),
     )
 }
 
+/// The string value of a `key = "value"` serde attribute, or of its `deserialize` form in
+/// `key(serialize = "..", deserialize = "..")`.
+fn deserialize_name(meta: &Meta) -> Option<String> {
+    match meta {
+        Meta::NameValue(nv) => match &nv.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(s), ..
+            }) => Some(s.value()),
+            _ => None,
+        },
+        Meta::List(list) => list
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .ok()?
+            .iter()
+            .find(|meta| meta.path().is_ident("deserialize"))
+            .and_then(deserialize_name),
+        Meta::Path(_) => None,
+    }
+}
+
+fn check_devolvable(ty: &Type) -> syn::Result<()> {
+    let reason = match ty {
+        Type::Path(p) => {
+            return p
+                .path
+                .segments
+                .iter()
+                .filter_map(|segment| match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => Some(&args.args),
+                    _ => None,
+                })
+                .flatten()
+                .try_for_each(|arg| match arg {
+                    GenericArgument::Type(ty) => check_devolvable(ty),
+                    _ => Ok(()),
+                })
+        }
+        Type::Array(a) => return check_devolvable(&a.elem),
+        Type::Tuple(t) => return t.elems.iter().try_for_each(check_devolvable),
+        Type::Group(g) => return check_devolvable(&g.elem),
+        Type::Paren(p) => return check_devolvable(&p.elem),
+        Type::Reference(_) => "a reference, since the devolved type must own its data",
+        Type::Ptr(_) => "a raw pointer",
+        Type::Slice(_) => "an unsized slice",
+        Type::BareFn(_) => "a function pointer",
+        Type::TraitObject(_) => "a trait object",
+        Type::ImplTrait(_) => "an `impl Trait` type",
+        Type::Never(_) => "the never type",
+        Type::Infer(_) => "an inferred type",
+        Type::Macro(_) => "a type macro, since its expansion cannot be inspected",
+        _ => "this type",
+    };
+
+    Err(syn::Error::new(
+        ty.span(),
+        format!(
+            "#[devo] cannot devolve {reason}; supported field types are paths such as `Fish`, `Vec<Fish>` or `crate::ocean::Fish`, and tuples or arrays of them, where each implements `serde_devo::Devolve`"
+        ),
+    ))
+}
+
+/// Rejects the `"none"` policy on a field which is not written as an `Option`.
+fn check_on_unknown(ty: &Type, on_unknown: Option<OnUnknown>) -> syn::Result<Option<OnUnknown>> {
+    fn is_option(ty: &Type) -> bool {
+        match ty {
+            Type::Path(p) => p
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Option"),
+            Type::Group(g) => is_option(&g.elem),
+            Type::Paren(p) => is_option(&p.elem),
+            _ => false,
+        }
+    }
+
+    if on_unknown != Some(OnUnknown::None) || is_option(ty) {
+        return Ok(on_unknown);
+    }
+
+    Err(syn::Error::new(
+        ty.span(),
+        "#[devo(on_unknown = \"none\")] requires an `Option` field, which is evolved as `None` when it contains unknown content; use \"default\" for other types implementing `Default`",
+    ))
+}
+
+/// Whether the data is a tuple struct, followed by its diagnostics, the body of the
+/// devolved type, and the bodies of its evolve, devolve, restore and error collecting
+/// implementations.
+type RenderedData = (
+    bool,
+    Vec<TokenStream>,
+    TokenStream,
+    TokenStream,
+    TokenStream,
+    TokenStream,
+    TokenStream,
+    TokenStream,
+    Vec<Type>,
+);
+
+#[derive(Clone, Copy, PartialEq)]
+enum Position {
+    Container,
+    Variant,
+    Field,
+}
+
+#[derive(Default)]
+struct DevoOptions {
+    fallback: Option<Type>,
+    fallbacks: Vec<Type>,
+    derives: Vec<syn::Path>,
+    capture_unknown_fields: bool,
+    catch_all: bool,
+    fallback_variant: Option<FallbackVariant>,
+    malformed_variant: Option<Ident>,
+    unknown: Option<Unknown>,
+    on_unknown: Option<OnUnknown>,
+    lenient: bool,
+    generic_fallback: Option<Ident>,
+}
+
+/// What becomes of a field containing unknown content when it is evolved.
+#[derive(Clone, Copy, PartialEq)]
+enum OnUnknown {
+    /// Fail to evolve the whole value, which is the default.
+    Error,
+    /// Leave out the elements of a sequence which contain unknown content.
+    Skip,
+    /// Evolve an `Option` field as `None`.
+    None,
+    /// Evolve the field as its `Default`.
+    Default,
+}
+
+/// How an unrecognized variant is evolved, instead of failing with `Error::UnknownVariant`.
+enum Unknown {
+    /// As the named unit variant of the evolved enum.
+    Variant(Ident),
+    /// By calling a constructor with the tag and content of the fallback variant.
+    With(syn::Path),
+}
+
+/// The name of the generated fallback variant, and of its field if it should be
+/// struct-like rather than a tuple variant.
+struct FallbackVariant {
+    name: Ident,
+    field: Option<Ident>,
+}
+
+impl DevoOptions {
+    fn parse(attrs: &[Attribute], devo_attr: &Ident, position: Position) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs {
+            let Meta::List(list) = &attr.meta else {
+                continue;
+            };
+            if list.path.get_ident() != Some(devo_attr) {
+                continue;
+            }
+
+            list.parse_nested_meta(|meta| {
+                if position == Position::Container && meta.path.is_ident("fallback") {
+                    let value = meta.value()?;
+                    if value.peek(syn::token::Bracket) {
+                        let list;
+                        syn::bracketed!(list in value);
+                        options.fallbacks = Punctuated::<Type, Token![,]>::parse_terminated(&list)?
+                            .into_iter()
+                            .collect();
+                    } else {
+                        options.fallback = Some(value.parse::<Type>()?);
+                    }
+                } else if position == Position::Container && meta.path.is_ident("generic_fallback")
+                {
+                    options.generic_fallback = Some(if meta.input.peek(Token![=]) {
+                        meta.value()?.parse::<Ident>()?
+                    } else {
+                        format_ident!("F")
+                    });
+                } else if position == Position::Container && meta.path.is_ident("derive") {
+                    meta.parse_nested_meta(|derive| {
+                        options.derives.push(derive.path);
+                        Ok(())
+                    })?;
+                } else if position == Position::Field && meta.path.is_ident("lenient") {
+                    options.lenient = true;
+                } else if position == Position::Field && meta.path.is_ident("on_unknown") {
+                    let policy = meta.value()?.parse::<syn::LitStr>()?;
+                    options.on_unknown = Some(match policy.value().as_str() {
+                        "error" => OnUnknown::Error,
+                        "skip" => OnUnknown::Skip,
+                        "none" => OnUnknown::None,
+                        "default" => OnUnknown::Default,
+                        _ => {
+                            return Err(syn::Error::new(
+                                policy.span(),
+                                "expected one of \"error\", \"skip\", \"none\" or \"default\"",
+                            ))
+                        }
+                    });
+                } else if position != Position::Field
+                    && meta.path.is_ident("capture_unknown_fields")
+                {
+                    options.capture_unknown_fields = true;
+                } else if position == Position::Variant && meta.path.is_ident("catch_all") {
+                    options.catch_all = true;
+                } else if position == Position::Container && meta.path.is_ident("unknown") {
+                    if meta.input.peek(Token![=]) {
+                        options.unknown = Some(Unknown::Variant(meta.value()?.parse()?));
+                    } else {
+                        meta.parse_nested_meta(|nested| {
+                            if nested.path.is_ident("with") {
+                                options.unknown = Some(Unknown::With(nested.value()?.parse()?));
+                                Ok(())
+                            } else {
+                                Err(nested.error("expected `with`"))
+                            }
+                        })?;
+                    }
+                } else if position == Position::Container && meta.path.is_ident("fallback_variant")
+                {
+                    if meta.input.peek(Token![=]) {
+                        let name = meta.value()?.parse::<Ident>()?;
+                        options.fallback_variant = Some(FallbackVariant { name, field: None });
+                    } else {
+                        let (mut name, mut field) = (None, None);
+                        meta.parse_nested_meta(|nested| {
+                            if nested.path.is_ident("name") {
+                                name = Some(nested.value()?.parse::<Ident>()?);
+                            } else if nested.path.is_ident("field") {
+                                field = Some(nested.value()?.parse::<Ident>()?);
+                            } else {
+                                return Err(nested.error("expected `name` or `field`"));
+                            }
+                            Ok(())
+                        })?;
+                        let name = name.unwrap_or_else(|| format_ident!("UnrecognizedVariant"));
+                        options.fallback_variant = Some(FallbackVariant { name, field });
+                    }
+                } else if position == Position::Container && meta.path.is_ident("malformed_variant")
+                {
+                    options.malformed_variant = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported devo attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+fn without_metas(attr: &Attribute, names: &[&str]) -> Option<TokenStream> {
+    let Ok(metas) = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) else {
+        return Some(attr.to_token_stream());
+    };
+    let metas = metas
+        .into_iter()
+        .filter(|meta| !names.iter().any(|name| meta.path().is_ident(name)))
+        .collect::<Vec<_>>();
+
+    (!metas.is_empty()).then(|| quote! { #[serde(#(#metas),*)] })
+}
+
 fn render_attrs(
     attrs: impl IntoIterator<Item = Attribute>,
     devo_attr: &Ident,
@@ -2,6 +2,114 @@
 #[doc = include_str!("../../README.md")]
 struct Readme;
 
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// struct Borrowed<'a> {
+///     #[devo]
+///     name: &'a str,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// enum Fish {
+///     OneFish,
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// struct Named<'a>(#[devo] (Fish, &'a str));
+/// ```
+///
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// struct Names<'a> {
+///     #[devo]
+///     names: Vec<&'a str>,
+/// }
+/// ```
+#[cfg(doctest)]
+struct UnsupportedFieldType;
+
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// enum Fish {
+///     OneFish,
+///     MalformedVariant,
+/// }
+/// ```
+///
+/// ```
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// #[devo(malformed_variant = BrokenFish)]
+/// enum Fish {
+///     OneFish,
+///     MalformedVariant,
+/// }
+/// ```
+#[cfg(doctest)]
+struct GeneratedVariantClash;
+
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// #[devo(unknown = Nonexistent)]
+/// enum Fish {
+///     OneFish,
+///     #[serde(untagged)]
+///     #[devo(catch_all)]
+///     OtherFish(serde_devo::Value),
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// #[devo(unknown = OneFish)]
+/// enum Fish {
+///     OneFish,
+///     #[serde(untagged)]
+///     #[devo(catch_all)]
+///     OtherFish(serde_devo::Value),
+/// }
+/// ```
+#[cfg(doctest)]
+struct UnknownWithCatchAll;
+
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// #[serde(deny_unknown_fields)]
+/// enum Event {
+///     Created { id: u32 },
+///     #[devo(capture_unknown_fields)]
+///     Updated { id: u32 },
+/// }
+/// ```
+#[cfg(doctest)]
+struct CaptureWithDenyUnknownFields;
+
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// #[devo(fallback = (bool, bool, bool))]
+/// enum Fish {
+///     OneFish,
+/// }
+/// ```
+#[cfg(doctest)]
+struct FallbackWithoutImpl;
+
+/// ```compile_fail
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// enum Fish {
+///     OneFish,
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize, serde_devo::Devolve)]
+/// struct Tank {
+///     #[devo(on_unknown = "none")]
+///     fishes: Vec<Fish>,
+/// }
+/// ```
+#[cfg(doctest)]
+struct OnUnknownNoneWithoutOption;
+
 #[cfg(test)]
 mod type_coverage {
     use std::collections::VecDeque;
@@ -504,8 +612,17 @@ mod fallback {
         Baz,
     }
 
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Flags(bool, bool, bool);
+
+    impl serde_devo::Fallback for Flags {
+        fn variant_tag(&self, _: Option<&str>) -> Option<&str> {
+            None
+        }
+    }
+
     #[derive(Serialize, Deserialize, Devolve)]
-    #[devo(fallback = (bool, bool, bool))]
+    #[devo(fallback = Flags)]
     enum MyBrokenEnum {
         Foo,
         Bar,
@@ -535,3 +652,2753 @@ mod fallback {
         assert!(serde_json::from_str::<DevolvedMyBrokenEnum>(&json).is_err());
     }
 }
+
+#[cfg(test)]
+mod containers {
+    use std::collections::{BTreeMap, HashMap, VecDeque};
+
+    use indexmap::IndexMap;
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    enum Fish {
+        OneFish,
+        TwoFish,
+        RedFish,
+    }
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    enum NewFish {
+        OneFish,
+        TwoFish,
+        RedFish,
+        BlueFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct School {
+        #[devo]
+        fishes: Vec<Fish>,
+        #[devo]
+        queue: VecDeque<Fish>,
+        #[devo]
+        pair: [Fish; 2],
+        #[devo]
+        shoals: Vec<Vec<Fish>>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct NewSchool {
+        #[devo]
+        fishes: Vec<NewFish>,
+        #[devo]
+        queue: VecDeque<NewFish>,
+        #[devo]
+        pair: [NewFish; 2],
+        #[devo]
+        shoals: Vec<Vec<NewFish>>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct Pond(#[devo] std::vec::Vec<Fish>, usize);
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+    enum Region {
+        North,
+        South,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct Aquarium {
+        #[devo]
+        tanks: HashMap<String, Fish>,
+        #[devo]
+        regions: BTreeMap<Region, Vec<Fish>>,
+        #[devo]
+        ordered: IndexMap<u32, Fish>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct NewAquarium {
+        #[devo]
+        tanks: HashMap<String, NewFish>,
+        #[devo]
+        regions: BTreeMap<Region, Vec<NewFish>>,
+        #[devo]
+        ordered: IndexMap<u32, NewFish>,
+    }
+
+    fn school() -> School {
+        School {
+            fishes: vec![Fish::OneFish, Fish::TwoFish],
+            queue: VecDeque::from([Fish::RedFish]),
+            pair: [Fish::TwoFish, Fish::OneFish],
+            shoals: vec![vec![], vec![Fish::RedFish, Fish::OneFish]],
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_through_devo() {
+        let initial = school();
+        let json = serde_json::to_string(&initial.clone().into_devolved()).unwrap();
+        assert_eq!(json, serde_json::to_string(&initial).unwrap());
+        let devolved: DevolvedSchool = serde_json::from_str(&json).unwrap();
+        assert_eq!(initial, devolved.try_into_evolved().unwrap());
+
+        let initial = Pond(vec![Fish::RedFish], 1);
+        let mp = rmp_serde::to_vec(&initial.clone().into_devolved()).unwrap();
+        let devolved: DevolvedPond = rmp_serde::from_slice(&mp).unwrap();
+        assert_eq!(initial, devolved.try_into_evolved().unwrap());
+    }
+
+    #[test]
+    fn test_unknown_element() {
+        let mut new = NewSchool {
+            fishes: vec![NewFish::OneFish, NewFish::BlueFish],
+            queue: VecDeque::from([NewFish::RedFish]),
+            pair: [NewFish::TwoFish, NewFish::OneFish],
+            shoals: vec![vec![NewFish::OneFish]],
+        };
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedSchool = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            devolved.fishes.as_slice(),
+            [DevolvedFish::OneFish, DevolvedFish::UnrecognizedVariant(_)]
+        ));
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+        let err = devolved.try_into_evolved().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: School.fishes.1.[unknown_variant]"
+        );
+
+        new.fishes = vec![];
+        new.pair[1] = NewFish::BlueFish;
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedSchool = serde_json::from_str(&json).unwrap();
+        let err = devolved.try_into_evolved().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: School.pair.1.[unknown_variant]"
+        );
+
+        new.pair[1] = NewFish::OneFish;
+        new.shoals = vec![vec![], vec![NewFish::OneFish, NewFish::BlueFish]];
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedSchool = serde_json::from_str(&json).unwrap();
+        let err = devolved.try_into_evolved().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: School.shoals.1.1.[unknown_variant]"
+        );
+    }
+
+    #[test]
+    fn test_maps() {
+        let initial = Aquarium {
+            tanks: HashMap::from([("a".to_string(), Fish::OneFish)]),
+            regions: BTreeMap::from([(Region::North, vec![Fish::RedFish, Fish::TwoFish])]),
+            ordered: IndexMap::from([(2, Fish::TwoFish), (1, Fish::OneFish)]),
+        };
+        let json = serde_json::to_string(&initial.clone().into_devolved()).unwrap();
+        assert_eq!(json, serde_json::to_string(&initial).unwrap());
+        let devolved: DevolvedAquarium = serde_json::from_str(&json).unwrap();
+        assert_eq!(initial, devolved.try_into_evolved().unwrap());
+
+        let mut new = NewAquarium {
+            tanks: HashMap::from([("big tank".to_string(), NewFish::BlueFish)]),
+            regions: BTreeMap::new(),
+            ordered: IndexMap::new(),
+        };
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedAquarium = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Aquarium.tanks.big tank.[unknown_variant]"
+        );
+
+        new.tanks.clear();
+        new.regions.insert(Region::South, vec![NewFish::BlueFish]);
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedAquarium = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Aquarium.regions.South.0.[unknown_variant]"
+        );
+
+        new.regions.clear();
+        new.ordered.insert(7, NewFish::OneFish);
+        new.ordered.insert(9, NewFish::BlueFish);
+        let mp = rmp_serde::to_vec(&new).unwrap();
+        let devolved: DevolvedAquarium = rmp_serde::from_slice(&mp).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Aquarium.ordered.9.[unknown_variant]"
+        );
+    }
+}
+
+#[cfg(test)]
+mod wrappers {
+    use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(derive(Clone, Debug))]
+    enum Fish {
+        OneFish,
+        TwoFish,
+    }
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(derive(Clone, Debug))]
+    enum NewFish {
+        OneFish,
+        TwoFish,
+        BlueFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    enum Tree {
+        Leaf(#[devo] Fish),
+        Branch(#[devo] Box<Tree>, #[devo] Option<Box<Tree>>),
+    }
+
+    #[allow(clippy::vec_box)]
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct Tank<'a> {
+        #[devo]
+        maybe: Option<Fish>,
+        #[devo]
+        nested: Option<Vec<Box<Fish>>>,
+        #[devo]
+        shared: Rc<Fish>,
+        #[devo]
+        synced: Arc<Vec<Fish>>,
+        #[devo]
+        borrowed: Cow<'a, Fish>,
+    }
+
+    #[allow(clippy::vec_box)]
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct NewTank<'a> {
+        #[devo]
+        maybe: Option<NewFish>,
+        #[devo]
+        nested: Option<Vec<Box<NewFish>>>,
+        #[devo]
+        shared: Rc<NewFish>,
+        #[devo]
+        synced: Arc<Vec<NewFish>>,
+        #[devo]
+        borrowed: Cow<'a, NewFish>,
+    }
+
+    #[test]
+    fn test_roundtrip_through_devo() {
+        let fish = Fish::TwoFish;
+        let initial = Tank {
+            maybe: None,
+            nested: Some(vec![Box::new(Fish::OneFish)]),
+            shared: Rc::new(Fish::TwoFish),
+            synced: Arc::new(vec![Fish::OneFish, Fish::TwoFish]),
+            borrowed: Cow::Borrowed(&fish),
+        };
+        let json = serde_json::to_string(&initial.clone().into_devolved()).unwrap();
+        assert_eq!(json, serde_json::to_string(&initial).unwrap());
+        let devolved: DevolvedTank = serde_json::from_str(&json).unwrap();
+        assert_eq!(initial, devolved.try_into_evolved().unwrap());
+
+        let initial = Tree::Branch(
+            Box::new(Tree::Leaf(Fish::OneFish)),
+            Some(Box::new(Tree::Branch(
+                Box::new(Tree::Leaf(Fish::TwoFish)),
+                None,
+            ))),
+        );
+        let mp = rmp_serde::to_vec(&initial.clone().into_devolved()).unwrap();
+        let devolved: DevolvedTree = rmp_serde::from_slice(&mp).unwrap();
+        assert_eq!(initial, devolved.try_into_evolved().unwrap());
+    }
+
+    #[test]
+    fn test_unknown_through_wrappers() {
+        let new = NewTank {
+            maybe: Some(NewFish::OneFish),
+            nested: Some(vec![
+                Box::new(NewFish::OneFish),
+                Box::new(NewFish::BlueFish),
+            ]),
+            shared: Rc::new(NewFish::TwoFish),
+            synced: Arc::new(vec![]),
+            borrowed: Cow::Owned(NewFish::OneFish),
+        };
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedTank = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Tank.nested.1.[unknown_variant]"
+        );
+
+        let new = NewTank {
+            nested: None,
+            shared: Rc::new(NewFish::BlueFish),
+            ..new
+        };
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedTank = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            *devolved.shared,
+            DevolvedFish::UnrecognizedVariant(_)
+        ));
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Tank.shared.[unknown_variant]"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tuples {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    enum Fish {
+        OneFish,
+        TwoFish,
+    }
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    enum NewFish {
+        OneFish,
+        TwoFish,
+        BlueFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    enum ErrorKind {
+        Escaped,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    enum NewErrorKind {
+        Escaped,
+        Eaten,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct Catch {
+        #[devo]
+        pair: (Fish, Fish),
+        #[devo]
+        tagged: (u32, Fish, String),
+        #[devo]
+        outcome: Result<Fish, ErrorKind>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct NewCatch {
+        #[devo]
+        pair: (NewFish, NewFish),
+        #[devo]
+        tagged: (u32, NewFish, String),
+        #[devo]
+        outcome: Result<NewFish, NewErrorKind>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct Net(#[devo] Vec<(Fish, Option<Fish>)>);
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Timeout {
+        after: std::time::Duration,
+    }
+
+    serde_devo::identity_impls!(Timeout);
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct Attempt {
+        #[devo]
+        outcome: Result<Fish, Timeout>,
+        #[devo]
+        peer: (std::net::IpAddr, std::path::PathBuf),
+    }
+
+    #[test]
+    fn test_roundtrip_through_devo() {
+        let initial = Catch {
+            pair: (Fish::OneFish, Fish::TwoFish),
+            tagged: (7, Fish::TwoFish, "seven".to_string()),
+            outcome: Err(ErrorKind::Escaped),
+        };
+        let json = serde_json::to_string(&initial.clone().into_devolved()).unwrap();
+        assert_eq!(json, serde_json::to_string(&initial).unwrap());
+        let devolved: DevolvedCatch = serde_json::from_str(&json).unwrap();
+        assert_eq!(initial, devolved.try_into_evolved().unwrap());
+
+        let initial = Net(vec![
+            (Fish::OneFish, None),
+            (Fish::TwoFish, Some(Fish::OneFish)),
+        ]);
+        let mp = rmp_serde::to_vec(&initial.clone().into_devolved()).unwrap();
+        let devolved: DevolvedNet = rmp_serde::from_slice(&mp).unwrap();
+        assert_eq!(initial, devolved.try_into_evolved().unwrap());
+    }
+
+    #[test]
+    fn test_user_error_and_std_types() {
+        for outcome in [
+            Ok(Fish::TwoFish),
+            Err(Timeout {
+                after: std::time::Duration::from_secs(3),
+            }),
+        ] {
+            let initial = Attempt {
+                outcome,
+                peer: ([127, 0, 0, 1].into(), "/tank".into()),
+            };
+            let json = serde_json::to_string(&initial.clone().into_devolved()).unwrap();
+            assert_eq!(json, serde_json::to_string(&initial).unwrap());
+            let devolved: DevolvedAttempt = serde_json::from_str(&json).unwrap();
+            assert_eq!(initial, devolved.try_into_evolved().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_unknown_positions() {
+        let mut new = NewCatch {
+            pair: (NewFish::OneFish, NewFish::BlueFish),
+            tagged: (7, NewFish::TwoFish, "seven".to_string()),
+            outcome: Ok(NewFish::OneFish),
+        };
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedCatch = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Catch.pair.1.[unknown_variant]"
+        );
+
+        new.pair.1 = NewFish::TwoFish;
+        new.outcome = Err(NewErrorKind::Eaten);
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedCatch = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Catch.outcome.Err.[unknown_variant]"
+        );
+    }
+}
+
+#[cfg(test)]
+mod paths {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+
+    mod ocean {
+        use serde::{Deserialize, Serialize};
+        use serde_devo::Devolve;
+
+        #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+        pub enum Fish {
+            OneFish,
+            TwoFish,
+        }
+
+        #[allow(clippy::enum_variant_names)]
+        #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+        pub enum NewFish {
+            OneFish,
+            TwoFish,
+            BlueFish,
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct Page<T>
+    where
+        T: Devolve,
+        <T as Devolve>::Devolved: for<'a> Deserialize<'a> + Serialize,
+    {
+        #[devo]
+        items: Vec<T>,
+        next: Option<u32>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct Catalog {
+        #[devo]
+        fish: ocean::Fish,
+        #[devo]
+        qualified: crate::paths::ocean::Fish,
+        #[devo]
+        page: Page<self::ocean::Fish>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    struct NewCatalog {
+        #[devo]
+        fish: ocean::NewFish,
+        #[devo]
+        qualified: crate::paths::ocean::NewFish,
+        #[devo]
+        page: Page<self::ocean::NewFish>,
+    }
+
+    #[test]
+    fn test_roundtrip_through_devo() {
+        let initial = Catalog {
+            fish: ocean::Fish::OneFish,
+            qualified: ocean::Fish::TwoFish,
+            page: Page {
+                items: vec![ocean::Fish::TwoFish],
+                next: Some(2),
+            },
+        };
+        let json = serde_json::to_string(&initial.clone().into_devolved()).unwrap();
+        assert_eq!(json, serde_json::to_string(&initial).unwrap());
+        let devolved: DevolvedCatalog = serde_json::from_str(&json).unwrap();
+        assert_eq!(initial, devolved.try_into_evolved().unwrap());
+    }
+
+    #[test]
+    fn test_unknown_in_generic_application() {
+        let new = NewCatalog {
+            fish: ocean::NewFish::OneFish,
+            qualified: ocean::NewFish::TwoFish,
+            page: Page {
+                items: vec![ocean::NewFish::TwoFish, ocean::NewFish::BlueFish],
+                next: None,
+            },
+        };
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedCatalog = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Catalog.page.items.1.[unknown_variant]"
+        );
+    }
+}
+
+#[cfg(test)]
+mod unknown_fields {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    enum Fish {
+        OneFish,
+        TwoFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[serde(deny_unknown_fields, rename_all = "camelCase")]
+    #[devo(fallback = serde_json::Value, capture_unknown_fields)]
+    struct Profile {
+        display_name: String,
+        #[devo]
+        favorite_fish: Fish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct NewProfile {
+        display_name: String,
+        favorite_fish: Fish,
+        tank_size: u32,
+        tags: Vec<String>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    enum Event {
+        Created {
+            id: u32,
+        },
+        #[devo(capture_unknown_fields)]
+        Updated {
+            id: u32,
+            #[devo]
+            fish: Fish,
+        },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    enum NewEvent {
+        Created { id: u32 },
+        Updated { id: u32, fish: Fish, reason: String },
+    }
+
+    #[test]
+    fn test_capture_struct_fields() {
+        let new = NewProfile {
+            display_name: "nemo".to_string(),
+            favorite_fish: Fish::TwoFish,
+            tank_size: 40,
+            tags: vec!["clown".to_string()],
+        };
+        let json = serde_json::to_string(&new).unwrap();
+        assert!(serde_json::from_str::<Profile>(&json).is_err());
+
+        let devolved: DevolvedProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            devolved.unknown_fields.keys().collect::<Vec<_>>(),
+            ["tankSize", "tags"]
+        );
+        assert_eq!(devolved.unknown_fields["tankSize"], serde_json::json!(40));
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+
+        let evolved = devolved.try_into_evolved().unwrap();
+        assert_eq!(
+            evolved,
+            Profile {
+                display_name: "nemo".to_string(),
+                favorite_fish: Fish::TwoFish,
+            }
+        );
+        assert!(evolved.into_devolved().unknown_fields.is_empty());
+
+        let mut cbor = vec![];
+        ciborium::into_writer(&new, &mut cbor).unwrap();
+        let devolved: DevolvedProfile = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(devolved.unknown_fields.len(), 2);
+    }
+
+    #[test]
+    fn test_capture_variant_fields() {
+        let new = NewEvent::Updated {
+            id: 3,
+            fish: Fish::OneFish,
+            reason: "moved".to_string(),
+        };
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedEvent = serde_json::from_str(&json).unwrap();
+        let DevolvedEvent::Updated { unknown_fields, .. } = &devolved else {
+            panic!("expected a known variant");
+        };
+        assert_eq!(unknown_fields["reason"], serde_json::json!("moved"));
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Event::Updated {
+                id: 3,
+                fish: Fish::OneFish
+            }
+        );
+
+        let json = serde_json::to_string(&NewEvent::Created { id: 4 }).unwrap();
+        let devolved: DevolvedEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Event::Created { id: 4 }
+        );
+    }
+}
+
+#[cfg(test)]
+mod preserved {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+    use serde_json::json;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(derive(Clone, Debug), capture_unknown_fields)]
+    struct Fish {
+        name: String,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(derive(Clone, Debug), capture_unknown_fields)]
+    struct Tank {
+        gallons: u32,
+        #[devo]
+        fishes: Vec<Fish>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(derive(Clone, Debug))]
+    enum Habitat {
+        Pond(#[devo] Fish),
+        #[devo(capture_unknown_fields)]
+        Reef {
+            depth: u32,
+            #[devo]
+            resident: Fish,
+        },
+    }
+
+    #[test]
+    fn test_preserve_nested_fields() {
+        let json = json!({
+            "gallons": 20,
+            "fishes": [
+                { "name": "nemo", "stripes": 3 },
+                { "name": "dory" },
+            ],
+            "filter": "sponge",
+        });
+        let devolved: DevolvedTank = serde_json::from_value(json).unwrap();
+
+        let mut tank = devolved.try_into_preserved().unwrap();
+        tank.gallons = 40;
+        tank.fishes[1].name = "marlin".to_string();
+        assert_eq!(tank.fishes.len(), 2);
+
+        assert_eq!(
+            serde_json::to_value(tank.into_devolved()).unwrap(),
+            json!({
+                "gallons": 40,
+                "fishes": [
+                    { "name": "nemo", "stripes": 3 },
+                    { "name": "marlin" },
+                ],
+                "filter": "sponge",
+            })
+        );
+    }
+
+    #[test]
+    fn test_preserve_variant_fields() {
+        let json = json!({ "Reef": { "depth": 9, "resident": { "name": "nemo", "stripes": 3 }, "coral": true } });
+        let devolved: DevolvedHabitat = serde_json::from_value(json).unwrap();
+
+        let mut habitat = devolved.clone().try_into_preserved().unwrap();
+        if let Habitat::Reef { depth, .. } = &mut *habitat {
+            *depth = 12;
+        }
+        assert_eq!(
+            serde_json::to_value(habitat.into_devolved()).unwrap(),
+            json!({ "Reef": { "depth": 12, "resident": { "name": "nemo", "stripes": 3 }, "coral": true } })
+        );
+
+        let mut habitat = devolved.try_into_preserved().unwrap();
+        *habitat = Habitat::Pond(Fish {
+            name: "dory".to_string(),
+        });
+        assert_eq!(
+            serde_json::to_value(habitat.into_devolved()).unwrap(),
+            json!({ "Pond": { "name": "dory" } })
+        );
+    }
+
+    #[test]
+    fn test_preserve_unknown_variant_fails() {
+        let devolved: DevolvedHabitat =
+            serde_json::from_value(json!({ "Lake": { "name": "nemo" } })).unwrap();
+        assert!(devolved.try_into_preserved().is_err());
+
+        let devolved: DevolvedTank = serde_json::from_value(json!({
+            "gallons": 20,
+            "fishes": [],
+            "filter": "sponge",
+        }))
+        .unwrap();
+        let tank = devolved.try_into_preserved().unwrap().into_inner();
+        assert!(tank.into_devolved().unknown_fields.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod recovery {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve, Value};
+    use serde_json::json;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Clone, Debug, PartialEq))]
+    enum Fish {
+        OneFish,
+        TwoFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Clone, Debug, PartialEq))]
+    struct Pond {
+        name: String,
+        #[devo]
+        fishes: BTreeMap<String, Vec<Fish>>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Clone, Debug, PartialEq))]
+    enum Habitat {
+        Puddle,
+        Lake(#[devo] Pond, #[devo] Option<Fish>),
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Clone, Debug, PartialEq))]
+    enum Report {
+        Failed {
+            #[devo]
+            errors: Vec<Fish>,
+        },
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Celsius(f32);
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Reading(f32);
+
+    impl Devolve<serde_json::Value> for Celsius {
+        type Devolved = Reading;
+
+        fn into_devolved(self) -> Reading {
+            Reading(self.0)
+        }
+    }
+
+    impl Evolve<serde_json::Value> for Reading {
+        type Evolved = Celsius;
+
+        fn try_into_evolved(self) -> Result<Celsius, serde_devo::Error> {
+            if self.0 < -273.15 {
+                return Err(serde_devo::Error::unknown_value("Celsius", &self.0));
+            }
+
+            Ok(Celsius(self.0))
+        }
+    }
+
+    #[test]
+    fn test_unknown_variant_payload() {
+        let devolved: DevolvedFish =
+            serde_json::from_value(json!({ "RedFish": { "spots": 2 } })).unwrap();
+        let err = devolved.try_into_evolved().unwrap_err();
+        assert!(matches!(
+            &err,
+            serde_devo::Error::UnknownVariant { ty, path, .. } if ty == "Fish" && path.is_empty()
+        ));
+        assert_eq!(
+            err.payload(),
+            &Value::serialized(&json!({ "RedFish": { "spots": 2 } })).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(err.payload()).unwrap(),
+            json!({ "RedFish": { "spots": 2 } })
+        );
+        assert_eq!(
+            err.payload()
+                .clone()
+                .deserialized::<serde_json::Value>()
+                .unwrap(),
+            json!({ "RedFish": { "spots": 2 } })
+        );
+
+        let devolved: DevolvedHabitat = serde_json::from_value(json!({
+            "Lake": [{ "name": "walden", "fishes": { "north": ["OneFish", "BlueFish"] } }, null]
+        }))
+        .unwrap();
+        let err = devolved.try_into_evolved().unwrap_err();
+        assert_eq!(err.payload(), &Value::String("BlueFish".to_string()));
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: Habitat.Lake.0.fishes.north.1.[unknown_variant]"
+        );
+    }
+
+    #[test]
+    fn test_recover_devolved_value() {
+        let value = json!({
+            "Lake": [{ "name": "walden", "fishes": { "north": ["OneFish"] } }, "GoldFish"]
+        });
+        let devolved: DevolvedHabitat = serde_json::from_value(value.clone()).unwrap();
+        let err = devolved.try_evolve().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: Habitat.Lake.1.[unknown_variant]"
+        );
+        assert_eq!(err.error.payload(), &Value::String("GoldFish".to_string()));
+        assert_eq!(serde_json::to_value(&err.devolved).unwrap(), value);
+
+        let devolved = err.devolved;
+        assert_eq!(
+            devolved.validate().unwrap_err().to_string(),
+            "evolution failed: Habitat.Lake.1.[unknown_variant]"
+        );
+
+        let devolved: DevolvedHabitat = serde_json::from_value(json!({
+            "Lake": [{ "name": "walden", "fishes": {} }, "TwoFish"]
+        }))
+        .unwrap();
+        assert!(devolved.validate().is_ok());
+        assert_eq!(
+            devolved.try_evolve().unwrap(),
+            Habitat::Lake(
+                Pond {
+                    name: "walden".to_string(),
+                    fishes: BTreeMap::new(),
+                },
+                Some(Fish::TwoFish)
+            )
+        );
+        let puddle: DevolvedHabitat = serde_json::from_value(json!("Puddle")).unwrap();
+        assert_eq!(puddle.try_evolve().unwrap(), Habitat::Puddle);
+    }
+
+    #[test]
+    fn test_recover_manual_impl() {
+        let reading = Reading(-300.0);
+        assert!(reading.validate().is_ok());
+
+        let err = reading.try_evolve().unwrap_err();
+        assert_eq!(err.devolved, Reading(-300.0));
+        assert_eq!(err.error.payload(), &Value::F32(-300.0));
+        assert_eq!(Reading(21.5).try_evolve().unwrap(), Celsius(21.5));
+    }
+
+    #[test]
+    fn test_collect_every_error() {
+        let devolved: DevolvedHabitat = serde_json::from_value(json!({
+            "Lake": [
+                {
+                    "name": "walden",
+                    "fishes": {
+                        "north": ["RedFish", "OneFish", "BlueFish"],
+                        "south": ["GoldFish"],
+                    },
+                },
+                "TwoFish",
+            ]
+        }))
+        .unwrap();
+        let errors = devolved.collect_errors();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| (e.pointer(), e.payload().as_str().unwrap().to_string()))
+                .collect::<Vec<_>>(),
+            [
+                ("/Lake/0/fishes/north/0".to_string(), "RedFish".to_string()),
+                ("/Lake/0/fishes/north/2".to_string(), "BlueFish".to_string()),
+                ("/Lake/0/fishes/south/0".to_string(), "GoldFish".to_string()),
+            ]
+        );
+        assert_eq!(
+            devolved.validate().unwrap_err().to_string(),
+            errors[0].to_string()
+        );
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            errors[0].to_string()
+        );
+
+        let devolved: DevolvedHabitat = serde_json::from_value(json!("Swamp")).unwrap();
+        assert_eq!(devolved.collect_errors().len(), 1);
+        let devolved: DevolvedHabitat = serde_json::from_value(json!("Puddle")).unwrap();
+        assert!(devolved.collect_errors().is_empty());
+    }
+
+    #[test]
+    fn test_collect_field_named_errors() {
+        let devolved: DevolvedReport =
+            serde_json::from_value(json!({ "Failed": { "errors": ["OneFish", "RedFish"] } }))
+                .unwrap();
+        let errors = devolved.collect_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pointer(), "/Failed/errors/1");
+    }
+}
+
+#[cfg(test)]
+mod error_paths {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve, Segment};
+    use serde_json::json;
+
+    #[derive(Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    enum Fish {
+        OneFish,
+        TwoFish,
+    }
+
+    #[derive(Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    struct Tag(#[devo] Fish);
+
+    #[derive(Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    #[serde(rename_all = "camelCase")]
+    struct Tank {
+        #[devo]
+        fish_by_name: BTreeMap<String, Fish>,
+        #[serde(rename = "résident")]
+        #[devo]
+        resident_fish: Vec<Tag>,
+        #[devo]
+        r#type: Option<Fish>,
+        #[serde(flatten)]
+        #[devo]
+        decor: Decor,
+    }
+
+    #[derive(Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    struct Decor {
+        #[devo]
+        castle_fish: Fish,
+    }
+
+    #[derive(Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    #[serde(rename_all = "snake_case", rename_all_fields = "kebab-case")]
+    enum Habitat {
+        CoralReef {
+            #[devo]
+            reef_fish: Fish,
+        },
+        #[serde(rename = "lake")]
+        FreshLake(#[devo] Fish, #[devo] Fish),
+        Pond(#[devo] Fish),
+    }
+
+    #[derive(Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    #[serde(tag = "kind")]
+    enum Internal {
+        Bowl {
+            #[devo]
+            fish: Fish,
+        },
+    }
+
+    #[derive(Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    #[serde(tag = "t", content = "c")]
+    enum Adjacent {
+        Bowl(#[devo] Fish, #[devo] Fish),
+    }
+
+    fn evolve_err<D>(value: serde_json::Value) -> serde_devo::Error
+    where
+        D: Evolve<serde_json::Value> + for<'de> Deserialize<'de>,
+    {
+        let devolved: D = serde_json::from_value(value).unwrap();
+        let err = devolved.validate().unwrap_err();
+        assert_eq!(
+            devolved.try_into_evolved().err().unwrap().pointer(),
+            err.pointer()
+        );
+        err
+    }
+
+    fn tank(overrides: serde_json::Value) -> serde_json::Value {
+        let mut tank = json!({
+            "fishByName": { "a/b~c": "OneFish" },
+            "résident": ["TwoFish"],
+            "type": null,
+            "castle_fish": "OneFish",
+        });
+        for (k, v) in overrides.as_object().unwrap() {
+            tank[k] = v.clone();
+        }
+        tank
+    }
+
+    #[test]
+    fn test_struct_paths() {
+        let err = evolve_err::<DevolvedTank>(tank(json!({ "fishByName": { "a/b~c": "RedFish" } })));
+        assert_eq!(err.pointer(), "/fishByName/a~1b~0c");
+        assert_eq!(
+            err.path(),
+            [
+                Segment::field("fishByName"),
+                Segment::Key("a/b~c".to_string())
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: Tank.fishByName.a/b~c.[unknown_variant]"
+        );
+
+        let err = evolve_err::<DevolvedTank>(tank(json!({ "résident": ["OneFish", "RedFish"] })));
+        assert_eq!(err.pointer(), "/résident/1");
+        assert_eq!(err.path(), [Segment::field("résident"), Segment::Index(1)]);
+
+        let err = evolve_err::<DevolvedTank>(tank(json!({ "type": "RedFish" })));
+        assert_eq!(err.pointer(), "/type");
+
+        let err = evolve_err::<DevolvedTank>(tank(json!({ "castle_fish": "RedFish" })));
+        assert_eq!(err.pointer(), "/castle_fish");
+    }
+
+    #[test]
+    fn test_enum_paths() {
+        let err =
+            evolve_err::<DevolvedHabitat>(json!({ "coral_reef": { "reef-fish": "RedFish" } }));
+        assert_eq!(err.pointer(), "/coral_reef/reef-fish");
+        assert_eq!(
+            err.path(),
+            [Segment::variant("coral_reef"), Segment::field("reef-fish")]
+        );
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: Habitat.coral_reef.reef-fish.[unknown_variant]"
+        );
+
+        let err = evolve_err::<DevolvedHabitat>(json!({ "lake": ["OneFish", "RedFish"] }));
+        assert_eq!(err.pointer(), "/lake/1");
+
+        let err = evolve_err::<DevolvedHabitat>(json!({ "pond": "RedFish" }));
+        assert_eq!(err.pointer(), "/pond");
+
+        let err = evolve_err::<DevolvedInternal>(json!({ "kind": "Bowl", "fish": "RedFish" }));
+        assert_eq!(err.pointer(), "/fish");
+
+        let err =
+            evolve_err::<DevolvedAdjacent>(json!({ "t": "Bowl", "c": ["OneFish", "RedFish"] }));
+        assert_eq!(err.pointer(), "/c/1");
+
+        let err = evolve_err::<DevolvedFish>(json!("RedFish"));
+        assert_eq!(err.pointer(), "");
+    }
+
+    #[test]
+    fn test_container_paths() {
+        let err = evolve_err::<Vec<BTreeMap<String, DevolvedTag>>>(json!([
+            { "a": "OneFish" },
+            { "b": "TwoFish", "c": "RedFish" },
+        ]));
+        assert_eq!(err.ty(), "Tag");
+        assert_eq!(
+            err.path(),
+            [Segment::Index(1), Segment::Key("c".to_string())]
+        );
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: Tag.1.c.[unknown_variant]"
+        );
+    }
+
+    #[test]
+    fn test_serialize_error() {
+        let err = evolve_err::<DevolvedTank>(tank(json!({ "résident": ["OneFish", "RedFish"] })));
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "kind": "unknown_variant",
+                "ty": "Tank",
+                "path": [{ "field": "résident" }, { "index": 1 }],
+                "tag": "RedFish",
+                "payload": "RedFish",
+            })
+        );
+
+        let json = String::from_utf8(serde_json::to_vec(&value).unwrap()).unwrap();
+        let restored: serde_devo::Error = serde_json::from_str(&json).unwrap();
+        drop(json);
+        assert_eq!(restored, err);
+        assert_eq!(restored.to_string(), err.to_string());
+
+        let mut cbor = vec![];
+        ciborium::into_writer(&err, &mut cbor).unwrap();
+        let restored: serde_devo::Error = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(restored, err);
+    }
+}
+
+#[cfg(test)]
+mod malformed {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+    use serde_json::json;
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Debug))]
+    #[serde(rename_all = "snake_case")]
+    enum Fish {
+        OneFish,
+        #[serde(alias = "crimson_fish")]
+        RedFish {
+            spots: u8,
+        },
+        BlueFish(#[devo] Box<Fish>),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Debug))]
+    #[serde(tag = "kind")]
+    enum Bowl {
+        Round { litres: u8 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Debug), malformed_variant = BrokenVariant)]
+    enum Report {
+        Fine(u8),
+        MalformedVariant,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Debug))]
+    #[serde(untagged)]
+    enum Depth {
+        Metres(u8),
+        Named { name: String },
+    }
+
+    #[test]
+    fn test_malformed_external_variant() {
+        let value = json!({ "red_fish": { "spots": "many" } });
+        let devolved: DevolvedFish = serde_json::from_value(value.clone()).unwrap();
+        let DevolvedFish::MalformedVariant { tag, content } = &devolved else {
+            panic!("expected a malformed variant, got {devolved:?}");
+        };
+        assert_eq!(tag, "red_fish");
+        assert_eq!(content, &value);
+        assert_eq!(serde_json::to_value(&devolved).unwrap(), value);
+
+        let err = devolved.try_into_evolved().unwrap_err();
+        assert!(
+            matches!(&err, serde_devo::Error::MalformedVariant { tag, .. } if tag == "red_fish")
+        );
+        assert_eq!(
+            err.to_string(),
+            "evolution failed: Fish.[malformed_variant:red_fish]"
+        );
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["kind"],
+            "malformed_variant"
+        );
+
+        let devolved: DevolvedFish = serde_json::from_value(json!({ "one_fish": 3 })).unwrap();
+        assert!(matches!(devolved, DevolvedFish::MalformedVariant { .. }));
+
+        let devolved: DevolvedFish =
+            serde_json::from_value(json!({ "blue_fish": { "red_fish": [] } })).unwrap();
+        let err = devolved.collect_errors().remove(0);
+        assert!(matches!(err, serde_devo::Error::MalformedVariant { .. }));
+        assert_eq!(err.pointer(), "/blue_fish");
+    }
+
+    #[test]
+    fn test_unknown_tags_are_unrecognized() {
+        for value in [
+            json!("gold_fish"),
+            json!({ "gold_fish": 1 }),
+            json!({ "a": 1, "b": 2 }),
+        ] {
+            let devolved: DevolvedFish = serde_json::from_value(value).unwrap();
+            assert!(matches!(devolved, DevolvedFish::UnrecognizedVariant(_)));
+            assert!(matches!(
+                devolved.try_into_evolved().unwrap_err(),
+                serde_devo::Error::UnknownVariant { .. }
+            ));
+        }
+
+        let devolved: DevolvedFish =
+            serde_json::from_value(json!({ "red_fish": { "spots": 2 } })).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Fish::RedFish { spots: 2 }
+        );
+    }
+
+    #[test]
+    fn test_malformed_internal_variant() {
+        let devolved: DevolvedBowl =
+            serde_json::from_value(json!({ "kind": "Round", "litres": -1 })).unwrap();
+        assert!(matches!(&devolved, DevolvedBowl::MalformedVariant { tag, .. } if tag == "Round"));
+
+        let devolved: DevolvedBowl =
+            serde_json::from_value(json!({ "kind": "Square", "litres": 1 })).unwrap();
+        assert!(
+            matches!(&devolved, DevolvedBowl::UnrecognizedVariant { tag, .. } if tag == "Square")
+        );
+
+        let mut cbor = vec![];
+        ciborium::into_writer(&json!({ "red_fish": { "spots": -1 } }), &mut cbor).unwrap();
+        let devolved: DevolvedFish = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert!(matches!(devolved, DevolvedFish::MalformedVariant { .. }));
+    }
+
+    #[test]
+    fn test_malformed_alias() {
+        let devolved: DevolvedFish =
+            serde_json::from_value(json!({ "crimson_fish": { "spots": "many" } })).unwrap();
+        assert!(matches!(
+            &devolved,
+            DevolvedFish::MalformedVariant { tag, .. } if tag == "crimson_fish"
+        ));
+    }
+
+    #[test]
+    fn test_renamed_malformed_variant() {
+        let devolved: DevolvedReport = serde_json::from_value(json!("MalformedVariant")).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Report::MalformedVariant
+        );
+
+        let devolved: DevolvedReport = serde_json::from_value(json!({ "Fine": -1 })).unwrap();
+        assert!(matches!(&devolved, DevolvedReport::BrokenVariant { tag, .. } if tag == "Fine"));
+    }
+
+    #[test]
+    fn test_untagged_content_is_unrecognized() {
+        let devolved: DevolvedDepth = serde_json::from_value(json!({ "Named": 1 })).unwrap();
+        assert!(matches!(devolved, DevolvedDepth::UnrecognizedVariant(_)));
+    }
+}
+
+#[cfg(test)]
+mod tags {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    enum Fish {
+        OneFish,
+        TwoFish { count: u8 },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum NewFish {
+        RedFish,
+        BlueFish { count: u8 },
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = ciborium::Value)]
+    #[serde(tag = "kind")]
+    enum CborFish {
+        OneFish { count: u8 },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "kind")]
+    enum NewCborFish {
+        GoldFish { count: u8 },
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = rmpv::Value)]
+    #[serde(tag = "t", content = "c")]
+    enum PackFish {
+        OneFish(u8),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = rmpv::Value)]
+    enum ExternalPackFish {
+        OneFish,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "t", content = "c")]
+    enum NewPackFish {
+        SilverFish(u8),
+    }
+
+    #[cfg(feature = "yaml")]
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_yaml::Value)]
+    enum YamlFish {
+        OneFish,
+    }
+
+    #[cfg(feature = "yaml")]
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_yaml::Value)]
+    #[serde(tag = "kind")]
+    enum InternalYamlFish {
+        OneFish { count: u8 },
+    }
+
+    #[cfg(feature = "toml")]
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = toml::Value)]
+    #[serde(tag = "kind")]
+    enum TomlFish {
+        OneFish { count: u8 },
+    }
+
+    #[cfg(feature = "ron")]
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = ron::Value)]
+    enum RonFish {
+        OneFish,
+    }
+
+    #[cfg(feature = "ron")]
+    #[derive(Debug, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = ron::Value)]
+    #[serde(tag = "kind")]
+    enum InternalRonFish {
+        OneFish { count: u8 },
+    }
+
+    #[test]
+    fn test_json_tags() {
+        let devolved: DevolvedFish =
+            serde_json::from_str(&serde_json::to_string(&NewFish::RedFish).unwrap()).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("RedFish"));
+
+        let json = serde_json::to_string(&NewFish::BlueFish { count: 2 }).unwrap();
+        let devolved: DevolvedFish = serde_json::from_str(&json).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("BlueFish"));
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().tag(),
+            Some("BlueFish")
+        );
+
+        let devolved: DevolvedFish = serde_json::from_str(r#""OneFish""#).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), None);
+
+        let devolved: DevolvedFish = serde_json::from_str("[1, 2]").unwrap();
+        assert_eq!(devolved.unrecognized_tag(), None);
+        assert_eq!(devolved.try_into_evolved().unwrap_err().tag(), None);
+    }
+
+    #[test]
+    fn test_cbor_tags() {
+        let mut cbor = vec![];
+        ciborium::into_writer(&NewCborFish::GoldFish { count: 1 }, &mut cbor).unwrap();
+        let devolved: DevolvedCborFish = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("GoldFish"));
+        assert!(matches!(
+            devolved.try_into_evolved().unwrap_err(),
+            serde_devo::Error::UnknownVariant { tag: Some(tag), .. } if tag == "GoldFish"
+        ));
+    }
+
+    #[test]
+    fn test_msgpack_tags() {
+        let pack = rmp_serde::to_vec_named(&NewPackFish::SilverFish(3)).unwrap();
+        let devolved: DevolvedPackFish = rmp_serde::from_slice(&pack).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("SilverFish"));
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().tag(),
+            Some("SilverFish")
+        );
+
+        let pack = rmp_serde::to_vec(&NewFish::BlueFish { count: 2 }).unwrap();
+        let devolved: DevolvedExternalPackFish = rmp_serde::from_slice(&pack).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("BlueFish"));
+
+        // the error reports the same tag as `unrecognized_tag`, so an integer key is none
+        let pack = rmp_serde::to_vec(&std::collections::BTreeMap::from([(1, 2)])).unwrap();
+        let devolved: DevolvedExternalPackFish = rmp_serde::from_slice(&pack).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), None);
+        assert_eq!(devolved.try_into_evolved().unwrap_err().tag(), None);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_tags() {
+        let yaml = serde_yaml::to_string(&NewFish::RedFish).unwrap();
+        let devolved: DevolvedYamlFish = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("RedFish"));
+        assert_eq!(serde_yaml::to_string(&devolved).unwrap(), yaml);
+
+        // `serde_yaml` writes variants with content as YAML tags, which serde cannot buffer
+        // while trying each variant.
+        let yaml = serde_yaml::to_string(&NewFish::BlueFish { count: 2 }).unwrap();
+        assert!(serde_yaml::from_str::<DevolvedYamlFish>(&yaml).is_err());
+
+        let yaml = serde_yaml::to_string(&NewCborFish::GoldFish { count: 1 }).unwrap();
+        let devolved: DevolvedInternalYamlFish = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("GoldFish"));
+        assert_eq!(serde_yaml::to_string(&devolved).unwrap(), yaml);
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().tag(),
+            Some("GoldFish")
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_tags() {
+        let toml = toml::to_string(&NewCborFish::GoldFish { count: 1 }).unwrap();
+        let devolved: DevolvedTomlFish = toml::from_str(&toml).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("GoldFish"));
+        assert_eq!(toml::to_string(&devolved).unwrap(), toml);
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().tag(),
+            Some("GoldFish")
+        );
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn test_ron_tags() {
+        // `ron::Value` drops the names of enum variants, so an unrecognized externally
+        // tagged variant is captured without its tag.
+        let ron = ron::to_string(&NewFish::RedFish).unwrap();
+        let devolved: DevolvedRonFish = ron::from_str(&ron).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), None);
+        assert_eq!(ron::to_string(&devolved).unwrap(), "()");
+
+        let ron = ron::to_string(&NewCborFish::GoldFish { count: 1 }).unwrap();
+        let devolved: DevolvedInternalRonFish = ron::from_str(&ron).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("GoldFish"));
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().tag(),
+            Some("GoldFish")
+        );
+    }
+}
+
+#[cfg(test)]
+mod representations {
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve, UnknownFields};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Fins {
+        count: u8,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    enum Fish {
+        Carp,
+        Koi { size: u8 },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum NewFish {
+        Minnow,
+        Tetra(u8),
+        Guppy { fins: u8, tail: String },
+        Molly(u8, u8),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Debug))]
+    #[serde(tag = "type")]
+    enum InternalFish {
+        Carp,
+        Koi { size: u8 },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    enum NewInternalFish {
+        Minnow,
+        Tetra(Fins),
+        Guppy { fins: u8, tail: String },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = ciborium::Value)]
+    #[serde(tag = "type")]
+    enum CborFish {
+        Carp,
+        Koi { size: u8 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Debug))]
+    #[serde(tag = "t", content = "c")]
+    enum AdjacentFish {
+        Carp,
+        Koi { size: u8 },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "t", content = "c")]
+    enum NewAdjacentFish {
+        Minnow,
+        Tetra(u8),
+        Guppy { fins: u8, tail: String },
+        Molly(u8, u8),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = rmpv::Value)]
+    #[serde(tag = "t", content = "c")]
+    enum PackFish {
+        Carp,
+        Koi { size: u8 },
+    }
+
+    fn json_round_trip<D: Serialize + DeserializeOwned>(value: &impl Serialize) -> D {
+        let json = serde_json::to_string(value).unwrap();
+        let devolved: D = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+        devolved
+    }
+
+    fn cbor_round_trip<D: Serialize + DeserializeOwned>(value: &impl Serialize) -> D {
+        let mut cbor = vec![];
+        ciborium::into_writer(value, &mut cbor).unwrap();
+        let devolved: D = ciborium::from_reader(cbor.as_slice()).unwrap();
+        let mut reserialized = vec![];
+        ciborium::into_writer(&devolved, &mut reserialized).unwrap();
+        assert_eq!(reserialized, cbor);
+        devolved
+    }
+
+    fn msgpack_round_trip<D: Serialize + DeserializeOwned>(value: &impl Serialize) -> D {
+        let pack = rmp_serde::to_vec_named(value).unwrap();
+        let devolved: D = rmp_serde::from_slice(&pack).unwrap();
+        assert_eq!(rmp_serde::to_vec_named(&devolved).unwrap(), pack);
+        devolved
+    }
+
+    #[test]
+    fn test_external_round_trip() {
+        for value in [
+            NewFish::Minnow,
+            NewFish::Tetra(4),
+            NewFish::Guppy {
+                fins: 2,
+                tail: "fan".into(),
+            },
+            NewFish::Molly(1, 2),
+        ] {
+            let devolved: DevolvedFish = json_round_trip(&value);
+            assert!(matches!(devolved, DevolvedFish::UnrecognizedVariant(_)));
+        }
+
+        let devolved: DevolvedFish = json_round_trip(&Fish::Koi { size: 3 });
+        assert_eq!(devolved.try_into_evolved().unwrap(), Fish::Koi { size: 3 });
+    }
+
+    #[test]
+    fn test_internal_round_trip() {
+        let devolved: DevolvedInternalFish = json_round_trip(&NewInternalFish::Guppy {
+            fins: 2,
+            tail: "fan".into(),
+        });
+        let DevolvedInternalFish::UnrecognizedVariant { tag, content } = &devolved else {
+            panic!("expected an unrecognized variant, got {devolved:?}");
+        };
+        assert_eq!(tag, "Guppy");
+        assert_eq!(content.keys().collect::<Vec<_>>(), ["fins", "tail"]);
+        assert_eq!(content["tail"], json!("fan"));
+        assert_eq!(devolved.unrecognized_tag(), Some("Guppy"));
+
+        let devolved: DevolvedInternalFish = json_round_trip(&NewInternalFish::Minnow);
+        assert!(matches!(
+            &devolved,
+            DevolvedInternalFish::UnrecognizedVariant { content, .. } if content.is_empty()
+        ));
+        let devolved: DevolvedInternalFish =
+            json_round_trip(&NewInternalFish::Tetra(Fins { count: 4 }));
+        let err = devolved.try_into_evolved().unwrap_err();
+        assert_eq!(err.tag(), Some("Tetra"));
+
+        let devolved: DevolvedInternalFish = json_round_trip(&InternalFish::Carp);
+        assert_eq!(devolved.try_into_evolved().unwrap(), InternalFish::Carp);
+
+        let devolved: DevolvedCborFish = cbor_round_trip(&NewInternalFish::Guppy {
+            fins: 2,
+            tail: "fan".into(),
+        });
+        assert_eq!(devolved.unrecognized_tag(), Some("Guppy"));
+        let devolved: DevolvedCborFish = cbor_round_trip(&CborFish::Koi { size: 3 });
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            CborFish::Koi { size: 3 }
+        );
+
+        let content = UnknownFields::<serde_json::Value>::new();
+        let devolved = DevolvedInternalFish::UnrecognizedVariant {
+            tag: "Minnow".into(),
+            content,
+        };
+        assert_eq!(
+            serde_json::to_value(&devolved).unwrap(),
+            json!({ "type": "Minnow" })
+        );
+    }
+
+    #[test]
+    fn test_adjacent_round_trip() {
+        for value in [
+            NewAdjacentFish::Minnow,
+            NewAdjacentFish::Tetra(4),
+            NewAdjacentFish::Guppy {
+                fins: 2,
+                tail: "fan".into(),
+            },
+            NewAdjacentFish::Molly(1, 2),
+        ] {
+            let devolved: DevolvedAdjacentFish = json_round_trip(&value);
+            assert!(matches!(
+                devolved,
+                DevolvedAdjacentFish::UnrecognizedVariant { .. }
+            ));
+            let devolved: DevolvedPackFish = msgpack_round_trip(&value);
+            assert!(matches!(
+                devolved,
+                DevolvedPackFish::UnrecognizedVariant { .. }
+            ));
+        }
+
+        let devolved: DevolvedAdjacentFish = json_round_trip(&NewAdjacentFish::Tetra(4));
+        let DevolvedAdjacentFish::UnrecognizedVariant { tag, content, .. } = &devolved else {
+            panic!("expected an unrecognized variant, got {devolved:?}");
+        };
+        assert_eq!(tag, "Tetra");
+        assert_eq!(content, &Some(json!(4)));
+
+        let devolved: DevolvedAdjacentFish = json_round_trip(&NewAdjacentFish::Minnow);
+        assert!(matches!(
+            &devolved,
+            DevolvedAdjacentFish::UnrecognizedVariant { content: None, .. }
+        ));
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().tag(),
+            Some("Minnow")
+        );
+
+        let devolved: DevolvedPackFish = msgpack_round_trip(&PackFish::Koi { size: 3 });
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            PackFish::Koi { size: 3 }
+        );
+    }
+
+    #[test]
+    fn test_tagged_key_order() {
+        let json = r#"{"fins":2,"type":"Guppy","tail":"fan"}"#;
+        let devolved: DevolvedInternalFish = serde_json::from_str(json).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("Guppy"));
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+
+        let json = r#"{"c":4,"extra":true,"t":"Tetra"}"#;
+        let devolved: DevolvedAdjacentFish = serde_json::from_str(json).unwrap();
+        let DevolvedAdjacentFish::UnrecognizedVariant {
+            tag,
+            content,
+            unknown_fields,
+        } = &devolved
+        else {
+            panic!("expected an unrecognized variant, got {devolved:?}");
+        };
+        assert_eq!(tag, "Tetra");
+        assert_eq!(content, &Some(json!(4)));
+        assert_eq!(unknown_fields["extra"], json!(true));
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+
+        let mut extra = UnknownFields::new();
+        extra.insert("extra", json!(true));
+        assert_eq!(unknown_fields, &extra);
+
+        let devolved = DevolvedAdjacentFish::UnrecognizedVariant {
+            tag: "Tetra".into(),
+            content: Some(json!(4)),
+            unknown_fields: UnknownFields::new(),
+        };
+        assert_eq!(
+            serde_json::to_string(&devolved).unwrap(),
+            r#"{"t":"Tetra","c":4}"#
+        );
+    }
+
+    #[test]
+    fn test_untagged_content_is_rejected() {
+        assert!(serde_json::from_value::<DevolvedInternalFish>(json!({ "size": 3 })).is_err());
+        assert!(serde_json::from_value::<DevolvedAdjacentFish>(json!({ "c": 3 })).is_err());
+    }
+}
+
+#[cfg(test)]
+mod untagged {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(derive(Debug))]
+    enum Fish {
+        OneFish,
+        #[serde(untagged)]
+        Named(String),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    enum Bowl {
+        Round,
+        #[serde(untagged)]
+        #[devo(catch_all)]
+        Other(serde_json::Value),
+    }
+
+    #[test]
+    fn test_fallback_follows_untagged_variants() {
+        let devolved: DevolvedFish = serde_json::from_value(json!("Wanda")).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Fish::Named("Wanda".into())
+        );
+
+        let value = json!({ "RedFish": { "spots": 3 } });
+        let devolved: DevolvedFish = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("RedFish"));
+        assert_eq!(serde_json::to_value(&devolved).unwrap(), value);
+        assert!(matches!(
+            devolved.try_into_evolved().unwrap_err(),
+            serde_devo::Error::UnknownVariant { .. }
+        ));
+
+        let devolved: DevolvedFish = serde_json::from_value(json!({ "OneFish": 1 })).unwrap();
+        assert!(matches!(devolved, DevolvedFish::MalformedVariant { .. }));
+    }
+
+    #[test]
+    fn test_catch_all_variant() {
+        let value = json!({ "Square": [1, 2] });
+        let devolved: DevolvedBowl = serde_json::from_value(value.clone()).unwrap();
+        let bowl = match devolved {
+            DevolvedBowl::Round => unreachable!(),
+            DevolvedBowl::Other(other) => other,
+        };
+        assert_eq!(bowl, value);
+    }
+}
+
+#[cfg(test)]
+mod fallback_variant {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, fallback_variant = Other)]
+    enum Fish {
+        OneFish,
+        UnrecognizedVariant,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, fallback_variant(name = Unknown, field = value))]
+    enum Bowl {
+        Round { litres: u8 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, fallback_variant(name = Unknown, field = fields))]
+    #[serde(tag = "kind")]
+    enum Tank {
+        Glass { litres: u8 },
+    }
+
+    #[test]
+    fn test_renamed_fallback_variant() {
+        let devolved: DevolvedFish = serde_json::from_value(json!("UnrecognizedVariant")).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Fish::UnrecognizedVariant
+        );
+
+        let devolved: DevolvedFish = serde_json::from_value(json!("RedFish")).unwrap();
+        assert!(matches!(&devolved, DevolvedFish::Other(other) if other == "RedFish"));
+        assert_eq!(devolved.unrecognized_tag(), Some("RedFish"));
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().tag(),
+            Some("RedFish")
+        );
+    }
+
+    #[test]
+    fn test_struct_fallback_variant() {
+        let value = json!({ "Square": { "side": 2 } });
+        let devolved: DevolvedBowl = serde_json::from_value(value.clone()).unwrap();
+        assert!(matches!(&devolved, DevolvedBowl::Unknown { value: v } if *v == value));
+        assert_eq!(devolved.unrecognized_tag(), Some("Square"));
+        assert_eq!(serde_json::to_value(&devolved).unwrap(), value);
+
+        let value = json!({ "kind": "Plastic", "litres": 3 });
+        let devolved: DevolvedTank = serde_json::from_value(value.clone()).unwrap();
+        let DevolvedTank::Unknown { tag, fields } = &devolved else {
+            panic!("expected the fallback variant");
+        };
+        assert_eq!(tag, "Plastic");
+        assert_eq!(fields["litres"], 3);
+        assert_eq!(serde_json::to_value(&devolved).unwrap(), value);
+        assert!(matches!(
+            devolved.try_into_evolved().unwrap_err(),
+            serde_devo::Error::UnknownVariant { tag: Some(tag), .. } if tag == "Plastic"
+        ));
+    }
+}
+
+#[cfg(test)]
+mod unknown {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve, UnknownFields};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Clone), unknown = Other)]
+    enum Fish {
+        OneFish { count: u8 },
+        Other,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, unknown(with = Bowl::unknown))]
+    enum Bowl {
+        Round,
+        Unknown {
+            tag: Option<String>,
+            payload: serde_json::Value,
+        },
+    }
+
+    impl Bowl {
+        fn unknown(tag: Option<String>, payload: serde_json::Value) -> Self {
+            Self::Unknown { tag, payload }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value, derive(Clone), unknown(with = Tank::unknown))]
+    #[serde(tag = "kind")]
+    enum Tank {
+        Glass,
+        Unknown { name: String, fields: usize },
+    }
+
+    impl Tank {
+        fn unknown(tag: Option<String>, content: UnknownFields<serde_json::Value>) -> Self {
+            Self::Unknown {
+                name: tag.unwrap_or_default(),
+                fields: content.len(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_variant() {
+        let devolved: DevolvedFish = serde_json::from_value(json!({ "RedFish": 1 })).unwrap();
+        assert!(devolved.collect_errors().is_empty());
+        assert_eq!(devolved.try_into_evolved().unwrap(), Fish::Other);
+
+        let devolved: DevolvedFish =
+            serde_json::from_value(json!({ "OneFish": { "count": "many" } })).unwrap();
+        assert!(matches!(
+            devolved.try_into_evolved().unwrap_err(),
+            serde_devo::Error::MalformedVariant { .. }
+        ));
+    }
+
+    #[test]
+    fn test_unknown_constructor() {
+        let devolved: DevolvedBowl = serde_json::from_value(json!({ "Square": 2 })).unwrap();
+        assert!(devolved.validate().is_ok());
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Bowl::Unknown {
+                tag: Some("Square".into()),
+                payload: json!({ "Square": 2 }),
+            }
+        );
+
+        let devolved: DevolvedTank =
+            serde_json::from_value(json!({ "kind": "Plastic", "litres": 3, "lid": true })).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Tank::Unknown {
+                name: "Plastic".into(),
+                fields: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_restore_unknown_variant() {
+        let value = json!({ "RedFish": 1 });
+        let devolved: DevolvedFish = serde_json::from_value(value.clone()).unwrap();
+        let fish = devolved.try_into_preserved().unwrap();
+        assert_eq!(*fish, Fish::Other);
+        assert_eq!(serde_json::to_value(fish.into_devolved()).unwrap(), value);
+
+        let devolved: DevolvedFish = serde_json::from_value(value).unwrap();
+        let mut fish = devolved.try_into_preserved().unwrap();
+        *fish = Fish::OneFish { count: 2 };
+        assert_eq!(
+            serde_json::to_value(fish.into_devolved()).unwrap(),
+            json!({ "OneFish": { "count": 2 } })
+        );
+
+        // A constructed value cannot be traced back to the content it was made from.
+        let devolved: DevolvedTank =
+            serde_json::from_value(json!({ "kind": "Plastic", "litres": 3 })).unwrap();
+        let tank = devolved.try_into_preserved().unwrap();
+        assert_eq!(
+            serde_json::to_value(tank.into_devolved()).unwrap(),
+            json!({ "kind": "Unknown", "name": "Plastic", "fields": 1 })
+        );
+    }
+}
+
+#[cfg(test)]
+mod on_unknown {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+    use serde_json::json;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize, Devolve)]
+    enum Fish {
+        #[default]
+        OneFish,
+        TwoFish,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    struct Tank {
+        #[devo(on_unknown = "skip")]
+        fishes: Vec<Fish>,
+        #[devo(on_unknown = "none")]
+        favourite: Option<Fish>,
+        #[devo(on_unknown = "default")]
+        mascot: Fish,
+        #[devo(on_unknown = "error")]
+        cleaner: Fish,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    struct Aquarium {
+        #[devo]
+        tanks: Vec<Tank>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    enum Catch {
+        Sorted {
+            #[devo(on_unknown = "skip")]
+            dropped: Vec<Fish>,
+        },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(derive(Clone))]
+    #[serde(tag = "kind")]
+    enum Item {
+        #[devo(capture_unknown_fields)]
+        A { x: u8 },
+        #[devo(capture_unknown_fields)]
+        B { y: u8 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(derive(Clone))]
+    struct Shelf {
+        #[devo(on_unknown = "skip")]
+        items: Vec<Item>,
+    }
+
+    #[test]
+    fn test_lossy_fields() {
+        let tank: DevolvedTank = serde_json::from_value(json!({
+            "fishes": ["OneFish", "RedFish", "TwoFish", "BlueFish"],
+            "favourite": "RedFish",
+            "mascot": "GoldFish",
+            "cleaner": "TwoFish",
+        }))
+        .unwrap();
+        assert!(tank.collect_errors().is_empty());
+
+        let (tank, dropped) = tank.try_into_evolved_lossy().unwrap();
+        assert_eq!(
+            tank,
+            Tank {
+                fishes: vec![Fish::OneFish, Fish::TwoFish],
+                favourite: None,
+                mascot: Fish::OneFish,
+                cleaner: Fish::TwoFish,
+            }
+        );
+        assert_eq!(
+            dropped.iter().map(|e| e.pointer()).collect::<Vec<_>>(),
+            ["/fishes/1", "/fishes/3", "/favourite", "/mascot"]
+        );
+        assert_eq!(dropped[0].tag(), Some("RedFish"));
+    }
+
+    #[test]
+    fn test_error_policy() {
+        let tank: DevolvedTank = serde_json::from_value(json!({
+            "fishes": [],
+            "favourite": null,
+            "mascot": "OneFish",
+            "cleaner": "RedFish",
+        }))
+        .unwrap();
+        assert_eq!(tank.collect_errors().len(), 1);
+        assert_eq!(
+            tank.try_into_evolved_lossy().unwrap_err().pointer(),
+            "/cleaner"
+        );
+    }
+
+    #[test]
+    fn test_nested_drops() {
+        let aquarium: DevolvedAquarium = serde_json::from_value(json!({
+            "tanks": [
+                { "fishes": [], "favourite": null, "mascot": "OneFish", "cleaner": "TwoFish" },
+                { "fishes": ["RedFish"], "favourite": null, "mascot": "OneFish", "cleaner": "TwoFish" },
+            ],
+        }))
+        .unwrap();
+        let (aquarium, dropped) = aquarium.try_into_evolved_lossy().unwrap();
+        assert!(aquarium.tanks[1].fishes.is_empty());
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].pointer(), "/tanks/1/fishes/0");
+        assert_eq!(dropped[0].ty(), "Aquarium");
+    }
+
+    #[test]
+    fn test_field_named_dropped() {
+        let catch: DevolvedCatch =
+            serde_json::from_value(json!({ "Sorted": { "dropped": ["OneFish", "RedFish"] } }))
+                .unwrap();
+        let (catch, dropped) = catch.try_into_evolved_lossy().unwrap();
+        assert_eq!(
+            catch,
+            Catch::Sorted {
+                dropped: vec![Fish::OneFish]
+            }
+        );
+        assert_eq!(dropped[0].pointer(), "/Sorted/dropped/1");
+    }
+
+    #[test]
+    fn test_restore_skipped() {
+        let shelf: DevolvedShelf = serde_json::from_value(json!({
+            "items": [
+                { "kind": "A", "x": 1, "w": 0 },
+                { "kind": "C", "z": 3 },
+                { "kind": "B", "y": 2 },
+            ],
+        }))
+        .unwrap();
+        let mut shelf = shelf.try_into_preserved().unwrap();
+        assert_eq!(shelf.items, [Item::A { x: 1 }, Item::B { y: 2 }]);
+        shelf.items[0] = Item::A { x: 5 };
+
+        assert_eq!(
+            serde_json::to_value(shelf.into_devolved()).unwrap(),
+            json!({
+                "items": [
+                    { "kind": "A", "x": 5, "w": 0 },
+                    { "kind": "C", "z": 3 },
+                    { "kind": "B", "y": 2 },
+                ],
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod lenient {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve, Lenient};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    enum Fish {
+        OneFish,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    struct Tank {
+        #[devo(lenient)]
+        litres: u32,
+        #[devo(lenient, on_unknown = "default")]
+        temperature: f32,
+        #[devo(lenient, on_unknown = "none")]
+        label: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    struct Bowl(#[devo(lenient)] u8);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_json::Value)]
+    struct Pond {
+        #[devo]
+        fishes: Lenient<Vec<Fish>, serde_json::Value>,
+    }
+
+    #[test]
+    fn test_lenient_fields() {
+        let value = json!({ "litres": 40, "temperature": "warm", "label": 3 });
+        let devolved: DevolvedTank = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(devolved.litres, Lenient::Known(40));
+        assert_eq!(devolved.temperature, Lenient::Unknown(json!("warm")));
+        assert_eq!(serde_json::to_value(&devolved).unwrap(), value);
+        assert!(devolved.collect_errors().is_empty());
+
+        let (tank, dropped) = devolved.try_into_evolved_lossy().unwrap();
+        assert_eq!(
+            tank,
+            Tank {
+                litres: 40,
+                temperature: 0.0,
+                label: None,
+            }
+        );
+        assert_eq!(
+            dropped.iter().map(|e| e.pointer()).collect::<Vec<_>>(),
+            ["/temperature", "/label"]
+        );
+
+        let devolved: DevolvedBowl = serde_json::from_value(json!("big")).unwrap();
+        let err = devolved.try_into_evolved().unwrap_err();
+        assert!(matches!(err, serde_devo::Error::UnknownValue { .. }));
+        assert_eq!(err.payload(), &serde_devo::Value::String("big".to_string()));
+        assert_eq!(err.to_string(), "evolution failed: Bowl.[unknown_value]");
+        assert_eq!(serde_json::to_value(&err).unwrap()["kind"], "unknown_value");
+    }
+
+    #[test]
+    fn test_lenient_type() {
+        let devolved: DevolvedPond = serde_json::from_value(json!({ "fishes": 5 })).unwrap();
+        let pond = devolved.try_into_evolved().unwrap();
+        assert_eq!(pond.fishes, Lenient::Unknown(json!(5)));
+
+        let devolved: DevolvedPond =
+            serde_json::from_value(json!({ "fishes": ["RedFish"] })).unwrap();
+        assert!(devolved.fishes.unknown().is_none());
+        assert_eq!(devolved.collect_errors()[0].pointer(), "/fishes/0");
+
+        let pond = Pond {
+            fishes: vec![Fish::OneFish].into(),
+        };
+        assert_eq!(
+            serde_json::to_value(pond.into_devolved()).unwrap(),
+            json!({ "fishes": ["OneFish"] })
+        );
+    }
+}
+
+#[cfg(test)]
+mod value {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve, Value};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_devo::Value, derive(Debug))]
+    enum Shape {
+        Circle { radius: u32 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = serde_devo::Value, derive(Debug))]
+    #[serde(tag = "kind")]
+    enum Sign {
+        Stop,
+    }
+
+    fn to_cbor<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut bytes = vec![];
+        ciborium::into_writer(value, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_value_captures_content() {
+        let value: Value =
+            serde_json::from_value(json!({ "a": [1, -2, 2.5, null, "b"], "c": true })).unwrap();
+        assert_eq!(
+            value,
+            Value::Map(vec![
+                (
+                    Value::String("a".to_owned()),
+                    Value::Seq(vec![
+                        Value::U64(1),
+                        Value::I64(-2),
+                        Value::F64(2.5),
+                        Value::Unit,
+                        Value::String("b".to_owned()),
+                    ])
+                ),
+                (Value::String("c".to_owned()), Value::Bool(true)),
+            ])
+        );
+
+        let tagged = ciborium::Value::Tag(1, Box::new(ciborium::Value::Bytes(vec![1, 2, 3])));
+        let cbor = to_cbor(&tagged);
+        let value: Value = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(value, Value::Tag(1, Box::new(Value::Bytes(vec![1, 2, 3]))));
+        assert_eq!(to_cbor(&value), cbor);
+
+        let big = to_cbor(&u128::MAX);
+        let value: Value = ciborium::from_reader(big.as_slice()).unwrap();
+        assert_eq!(value, Value::U128(u128::MAX));
+        assert_eq!(to_cbor(&value), big);
+
+        let mp = rmp_serde::to_vec(&(Value::Bytes(vec![0xde, 0xad]), -7i8, "x")).unwrap();
+        let value: Value = rmp_serde::from_slice(&mp).unwrap();
+        assert_eq!(rmp_serde::to_vec(&value).unwrap(), mp);
+    }
+
+    #[test]
+    fn test_value_across_formats() {
+        let value = json!({ "Triangle": { "sides": [3, 4, 5], "label": "right" } });
+        let captured: Value = serde_json::from_value(value.clone()).unwrap();
+
+        let cbor = to_cbor(&captured);
+        let from_cbor: ciborium::Value = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(from_cbor, ciborium::Value::serialized(&value).unwrap());
+
+        let mp = rmp_serde::to_vec(&captured).unwrap();
+        let from_mp: Value = rmp_serde::from_slice(&mp).unwrap();
+        assert_eq!(serde_json::to_value(&from_mp).unwrap(), value);
+    }
+
+    #[test]
+    fn test_value_fallback() {
+        let value = json!({ "Triangle": { "sides": [3, 4, 5] } });
+        let devolved: DevolvedShape = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("Triangle"));
+        assert_eq!(serde_json::to_value(&devolved).unwrap(), value);
+        assert!(devolved.try_into_evolved().is_err());
+
+        let cbor = to_cbor(&value);
+        let devolved: DevolvedShape = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("Triangle"));
+        assert_eq!(to_cbor(&devolved), cbor);
+
+        let devolved: DevolvedShape =
+            serde_json::from_value(json!({ "Circle": { "radius": 2 } })).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Shape::Circle { radius: 2 }
+        );
+
+        let value = json!({ "kind": "Yield", "sides": 3 });
+        let devolved: DevolvedSign = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(devolved.unrecognized_tag(), Some("Yield"));
+        assert_eq!(serde_json::to_value(&devolved).unwrap(), value);
+    }
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_value_yaml_tag() {
+        let yaml = "!Triangle\nsides: 3\n";
+        let captured: Value = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            captured,
+            Value::Map(vec![(
+                Value::String("Triangle".to_owned()),
+                Value::Map(vec![(Value::String("sides".to_owned()), Value::U64(3))]),
+            )])
+        );
+
+        // the variant is serialized in its externally tagged form, not as a YAML tag
+        assert_eq!(
+            serde_yaml::to_string(&captured).unwrap(),
+            "Triangle:\n  sides: 3\n"
+        );
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn test_value_ron_variant() {
+        // RON drops the variant name when deserializing any value
+        let captured: Value = ron::from_str("Triangle(3)").unwrap();
+        assert_eq!(captured, Value::Seq(vec![Value::U8(3)]));
+    }
+}
+
+#[cfg(test)]
+mod generic_fallback {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(generic_fallback, derive(Debug))]
+    enum Fish {
+        OneFish,
+        TwoFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[serde(tag = "kind")]
+    #[devo(generic_fallback = V)]
+    enum Bowl {
+        Round { litres: u32 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(generic_fallback, capture_unknown_fields)]
+    struct Tank {
+        #[devo]
+        fishes: Vec<Fish>,
+        #[devo]
+        bowl: Bowl,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(generic_fallback)]
+    struct Shelf<V> {
+        label: V,
+        #[devo]
+        fishes: Vec<Fish>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(generic_fallback)]
+    #[serde(bound = "V: Serialize + serde::de::DeserializeOwned")]
+    struct Crate<V> {
+        label: V,
+        #[devo]
+        fish: Fish,
+    }
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum NewFish {
+        OneFish,
+        TwoFish,
+        RedFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "kind")]
+    enum NewBowl {
+        Round { litres: u32 },
+        Square { side: u32 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct NewTank {
+        fishes: Vec<NewFish>,
+        bowl: NewBowl,
+        salty: bool,
+    }
+
+    fn new_tank() -> NewTank {
+        NewTank {
+            fishes: vec![NewFish::OneFish, NewFish::RedFish],
+            bowl: NewBowl::Square { side: 2 },
+            salty: true,
+        }
+    }
+
+    fn check<F: serde_devo::Fallback>(devolved: &DevolvedTank<F>) {
+        assert_eq!(devolved.fishes[1].unrecognized_tag(), Some("RedFish"));
+        assert_eq!(devolved.bowl.unrecognized_tag(), Some("Square"));
+        assert_eq!(
+            devolved.unknown_fields.keys().collect::<Vec<_>>(),
+            ["salty"]
+        );
+        assert_eq!(
+            devolved.collect_errors()[0].to_string(),
+            "evolution failed: Tank.fishes.1.[unknown_variant]"
+        );
+    }
+
+    #[test]
+    fn test_one_derive_for_every_format() {
+        let json = serde_json::to_string(&new_tank()).unwrap();
+        let devolved: DevolvedTank<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        check(&devolved);
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+
+        let mut cbor = vec![];
+        ciborium::into_writer(&new_tank(), &mut cbor).unwrap();
+        let devolved: DevolvedTank<ciborium::Value> =
+            ciborium::from_reader(cbor.as_slice()).unwrap();
+        check(&devolved);
+
+        let mp = rmp_serde::to_vec_named(&new_tank()).unwrap();
+        let devolved: DevolvedTank<rmpv::Value> = rmp_serde::from_slice(&mp).unwrap();
+        check(&devolved);
+        assert_eq!(rmp_serde::to_vec_named(&devolved).unwrap(), mp);
+
+        let devolved: DevolvedTank<serde_devo::Value> = serde_json::from_str(&json).unwrap();
+        check(&devolved);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let tank = Tank {
+            fishes: vec![Fish::TwoFish],
+            bowl: Bowl::Round { litres: 4 },
+        };
+        let devolved: DevolvedTank<ciborium::Value> = tank.clone().into_devolved();
+        assert_eq!(devolved.try_into_evolved().unwrap(), tank);
+
+        let json = serde_json::to_string(&tank).unwrap();
+        let devolved: DevolvedTank<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(devolved.try_into_evolved().unwrap(), tank);
+        assert_eq!(
+            <Fish as Devolve<rmpv::Value>>::into_devolved(Fish::OneFish).try_into_evolved(),
+            Ok(Fish::OneFish)
+        );
+    }
+
+    #[test]
+    fn test_generic_container() {
+        let json = r#"{"label":3,"fishes":["OneFish","RedFish"]}"#;
+        let devolved: DevolvedShelf<u8, serde_json::Value> = serde_json::from_str(json).unwrap();
+        assert_eq!(devolved.label, 3);
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().pointer(),
+            "/fishes/1"
+        );
+
+        let json = r#"{"label":"top","fish":"TwoFish"}"#;
+        let devolved: DevolvedCrate<String, ciborium::Value> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            devolved.try_into_evolved().unwrap(),
+            Crate {
+                label: "top".to_string(),
+                fish: Fish::TwoFish,
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod fallback_list {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, Evolve};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = [serde_json::Value, ciborium::Value], derive(Debug))]
+    enum Fish {
+        OneFish,
+        TwoFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = [serde_json::Value, ciborium::Value], capture_unknown_fields)]
+    struct Tank {
+        #[devo]
+        fishes: Vec<Fish>,
+    }
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum NewFish {
+        OneFish,
+        TwoFish,
+        RedFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct NewTank {
+        fishes: Vec<NewFish>,
+        salty: bool,
+    }
+
+    #[test]
+    fn test_devolved_type_per_fallback() {
+        let new = NewTank {
+            fishes: vec![NewFish::OneFish, NewFish::RedFish],
+            salty: true,
+        };
+
+        let json = serde_json::to_string(&new).unwrap();
+        let devolved: DevolvedTankSerdeJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(devolved.fishes[1].unrecognized_tag(), Some("RedFish"));
+        assert_eq!(
+            devolved.unknown_fields["salty"],
+            serde_json::Value::Bool(true)
+        );
+        assert_eq!(serde_json::to_string(&devolved).unwrap(), json);
+
+        let mut cbor = vec![];
+        ciborium::into_writer(&new, &mut cbor).unwrap();
+        let devolved: DevolvedTankCiborium = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(devolved.fishes[1].unrecognized_tag(), Some("RedFish"));
+        assert_eq!(
+            devolved.unknown_fields["salty"],
+            ciborium::Value::Bool(true)
+        );
+        assert_eq!(
+            devolved.try_into_evolved().unwrap_err().to_string(),
+            "evolution failed: Tank.fishes.1.[unknown_variant]"
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let tank = Tank {
+            fishes: vec![Fish::OneFish, Fish::TwoFish],
+        };
+        let devolved = Devolve::<serde_json::Value>::into_devolved(tank.clone());
+        assert_eq!(devolved.try_into_evolved().unwrap(), tank);
+
+        let devolved = Devolve::<ciborium::Value>::into_devolved(tank.clone());
+        assert_eq!(devolved.try_into_evolved().unwrap(), tank);
+    }
+}
+
+#[cfg(test)]
+mod transcode {
+    use serde::{Deserialize, Serialize};
+    use serde_devo::{Devolve, MapFallback};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(generic_fallback, derive(Debug, Clone))]
+    enum Fish {
+        OneFish,
+        TwoFish,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[serde(tag = "kind")]
+    #[devo(generic_fallback, derive(Debug, Clone))]
+    enum Bowl {
+        Round { litres: u32 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(generic_fallback, capture_unknown_fields, derive(Debug, Clone))]
+    struct Tank {
+        #[devo]
+        fishes: Vec<Fish>,
+        #[devo]
+        bowl: Bowl,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Devolve)]
+    #[devo(fallback = [serde_json::Value, ciborium::Value], capture_unknown_fields)]
+    struct Pond {
+        #[devo]
+        fishes: Vec<Fish>,
+        #[devo(lenient)]
+        depth: u32,
+    }
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum NewFish {
+        OneFish,
+        TwoFish,
+        RedFish { spots: u32 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "kind")]
+    enum NewBowl {
+        Round { litres: u32 },
+        Square { side: u32 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct NewTank {
+        fishes: Vec<NewFish>,
+        bowl: NewBowl,
+        salty: bool,
+    }
+
+    fn to_cbor<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut cbor = vec![];
+        ciborium::into_writer(value, &mut cbor).unwrap();
+        cbor
+    }
+
+    #[test]
+    fn test_transcode_generic_fallback() {
+        let new = NewTank {
+            fishes: vec![NewFish::OneFish, NewFish::RedFish { spots: 3 }],
+            bowl: NewBowl::Square { side: 2 },
+            salty: true,
+        };
+        let devolved: DevolvedTank<ciborium::Value> =
+            ciborium::from_reader(to_cbor(&new).as_slice()).unwrap();
+
+        let json: DevolvedTank<serde_json::Value> = devolved.clone().transcode().unwrap();
+        assert_eq!(json.fishes[1].unrecognized_tag(), Some("RedFish"));
+        assert_eq!(json.bowl.unrecognized_tag(), Some("Square"));
+        assert_eq!(
+            serde_json::to_string(&json).unwrap(),
+            serde_json::to_string(&new).unwrap()
+        );
+
+        let value: DevolvedTank<serde_devo::Value> = devolved.clone().transcode().unwrap();
+        let cbor: DevolvedTank<ciborium::Value> = value.transcode().unwrap();
+        assert_eq!(to_cbor(&cbor), to_cbor(&devolved));
+    }
+
+    #[test]
+    fn test_transcode_fallback_list() {
+        let json = r#"{"fishes":["OneFish",{"RedFish":{"spots":3}}],"depth":"deep","salty":true}"#;
+        let devolved: DevolvedPondSerdeJson = serde_json::from_str(json).unwrap();
+
+        let cbor = devolved.transcode_into::<ciborium::Value>().unwrap();
+        assert_eq!(cbor.fishes[1].unrecognized_tag(), Some("RedFish"));
+        assert_eq!(
+            cbor.depth.unknown(),
+            Some(&ciborium::Value::Text("deep".into()))
+        );
+        assert_eq!(cbor.unknown_fields["salty"], ciborium::Value::Bool(true));
+
+        let mut fields = 0;
+        let mut count = |content| {
+            fields += 1;
+            serde_devo::transcode(content)
+        };
+        let json = cbor
+            .map_fallback_into::<serde_json::Value, _>(&mut count)
+            .unwrap();
+        assert_eq!(fields, 3);
+        assert_eq!(json.unknown_fields["salty"], serde_json::Value::Bool(true));
+    }
+
+    #[test]
+    fn test_unrepresentable_content() {
+        let mut devolved = Devolve::<ciborium::Value>::into_devolved(Tank {
+            fishes: vec![Fish::OneFish],
+            bowl: Bowl::Round { litres: 4 },
+        });
+        let stamp = ciborium::Value::Tag(1, Box::new(ciborium::Value::Integer(0.into())));
+        devolved.unknown_fields.insert("stamp", stamp);
+
+        let e = MapFallback::<_, serde_json::Value>::transcode(devolved.clone()).unwrap_err();
+        assert_eq!(e.ty(), "Tank");
+        assert_eq!(e.pointer(), "/stamp");
+        assert_eq!(
+            e.to_string(),
+            "transcoding failed: Tank.stamp: CBOR tag 1 cannot be represented: invalid type: enum, expected any valid JSON value"
+        );
+
+        let value: DevolvedTank<serde_devo::Value> = devolved.transcode().unwrap();
+        assert_eq!(
+            value.unknown_fields["stamp"],
+            serde_devo::Value::Tag(1, Box::new(serde_devo::Value::U8(0)))
+        );
+
+        let new = NewTank {
+            fishes: vec![NewFish::RedFish { spots: 3 }],
+            bowl: NewBowl::Round { litres: 4 },
+            salty: false,
+        };
+        let mut devolved: DevolvedTank<ciborium::Value> =
+            ciborium::from_reader(to_cbor(&new).as_slice()).unwrap();
+        let counts = ciborium::Value::Map(vec![(1.into(), "one".into())]);
+        devolved.unknown_fields.insert("counts", counts);
+
+        let e = MapFallback::<_, serde_json::Value>::transcode(devolved).unwrap_err();
+        assert_eq!(e.pointer(), "/counts/1");
+        assert_eq!(
+            e.message(),
+            "invalid type: integer `1`, expected a string key"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A self-describing value type which can hold the content of an unrecognized enum
+/// variant, and from which the variant's tag can be read.
+///
+/// Implemented for the format-agnostic [`Value`](crate::Value), and for the dynamic value
+/// types of `serde_json`, `ciborium`, `rmpv`, `serde_yaml`, `toml` and `ron` when the
+/// corresponding features are enabled.
+///
+/// Devolved types generated with `#[devo(generic_fallback)]` may hold any fallback type
+/// implementing this trait, and the fallback type named by `#[devo(fallback = Type)]` must
+/// implement it.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a fallback type",
+    label = "the fallback type must implement `serde_devo::Fallback`",
+    note = "use `serde_devo::Value` or the value type of a format feature, or implement `serde_devo::Fallback` for `{Self}`"
+)]
+pub trait Fallback: Serialize + DeserializeOwned + fmt::Debug {
+    /// The tag of the enum variant held in this value: the name of a unit variant or the
+    /// single key of an externally tagged variant when `tag_key` is `None`, and otherwise
+    /// the value of the `tag_key` entry of an internally or adjacently tagged variant.
+    fn variant_tag(&self, tag_key: Option<&str>) -> Option<&str>;
+}
+
+#[cfg(feature = "json")]
+impl Fallback for serde_json::Value {
+    fn variant_tag(&self, tag_key: Option<&str>) -> Option<&str> {
+        match (self, tag_key) {
+            (Self::String(tag), None) => Some(tag),
+            (Self::Object(map), None) if map.len() == 1 => map.keys().next().map(String::as_str),
+            (Self::Object(map), Some(key)) => map.get(key)?.as_str(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl Fallback for ciborium::Value {
+    fn variant_tag(&self, tag_key: Option<&str>) -> Option<&str> {
+        match (self, tag_key) {
+            (Self::Text(tag), None) => Some(tag),
+            (Self::Map(entries), None) if entries.len() == 1 => entries[0].0.as_text(),
+            (Self::Map(entries), Some(key)) => entries
+                .iter()
+                .find(|(k, _)| k.as_text() == Some(key))?
+                .1
+                .as_text(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl Fallback for rmpv::Value {
+    fn variant_tag(&self, tag_key: Option<&str>) -> Option<&str> {
+        match (self, tag_key) {
+            (Self::String(tag), None) => tag.as_str(),
+            (Self::Map(entries), None) if entries.len() == 1 => entries[0].0.as_str(),
+            (Self::Map(entries), Some(key)) => entries
+                .iter()
+                .find(|(k, _)| k.as_str() == Some(key))?
+                .1
+                .as_str(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl Fallback for serde_yaml::Value {
+    /// YAML tags such as `!RedFish` cannot be borrowed as a string, so the tag of a variant
+    /// written in that form is not found.
+    fn variant_tag(&self, tag_key: Option<&str>) -> Option<&str> {
+        match (self, tag_key) {
+            (Self::String(tag), None) => Some(tag),
+            (Self::Mapping(map), None) if map.len() == 1 => map.keys().next()?.as_str(),
+            (Self::Mapping(map), Some(key)) => map.get(key)?.as_str(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+impl Fallback for toml::Value {
+    fn variant_tag(&self, tag_key: Option<&str>) -> Option<&str> {
+        match (self, tag_key) {
+            (Self::String(tag), None) => Some(tag),
+            (Self::Table(table), None) if table.len() == 1 => {
+                table.keys().next().map(String::as_str)
+            }
+            (Self::Table(table), Some(key)) => table.get(key)?.as_str(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "ron")]
+impl Fallback for ron::Value {
+    fn variant_tag(&self, tag_key: Option<&str>) -> Option<&str> {
+        fn as_str(value: &ron::Value) -> Option<&str> {
+            match value {
+                ron::Value::String(s) => Some(s),
+                _ => None,
+            }
+        }
+
+        match (self, tag_key) {
+            (Self::String(tag), None) => Some(tag),
+            (Self::Map(map), None) if map.len() == 1 => as_str(map.keys().next()?),
+            (Self::Map(map), Some(key)) => map
+                .iter()
+                .find(|(k, _)| as_str(k) == Some(key))
+                .and_then(|(_, v)| as_str(v)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::{fmt, marker::PhantomData, ops::Index};
+
+use serde::{
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{MapFallback, Segment, TranscodeError};
+
+/// The fields of a devolved struct which were not recognized during deserialization,
+/// retained in the order they were received so that they may be serialized again.
+///
+/// Generated for structs and struct variants with the `#[devo(capture_unknown_fields)]`
+/// attribute, as a flattened `unknown_fields` member of the devolved type.
+///
+/// Also holds the remaining entries of an unrecognized internally or adjacently tagged
+/// variant, along with where its tag and content were received among them. Only the fields
+/// and their order are compared for equality, not where the split-off entries were received.
+#[derive(Debug, Clone)]
+pub struct UnknownFields<F>(
+    pub(crate) Vec<(String, F)>,
+    /// The positions in the received map of the entries split off from these fields, as
+    /// recorded by [`tagged`](crate::tagged), or empty when they came first.
+    pub(crate) Vec<usize>,
+);
+
+impl<F: PartialEq> PartialEq for UnknownFields<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<F> Default for UnknownFields<F> {
+    fn default() -> Self {
+        Self(vec![], vec![])
+    }
+}
+
+impl<F> UnknownFields<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&F> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Inserts a field, replacing the value of any existing field with the same name.
+    pub fn insert(&mut self, key: impl Into<String>, value: F) -> Option<F> {
+        let key = key.into();
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                self.0.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<F> {
+        let i = self.0.iter().position(|(k, _)| k == key)?;
+        Some(self.0.remove(i).1)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(k, _)| k.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &F)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+impl<F> Index<&str> for UnknownFields<F> {
+    type Output = F;
+
+    fn index(&self, key: &str) -> &F {
+        self.get(key)
+            .unwrap_or_else(|| panic!("no unknown field named `{key}`"))
+    }
+}
+
+impl<F> IntoIterator for UnknownFields<F> {
+    type Item = (String, F);
+    type IntoIter = std::vec::IntoIter<(String, F)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<F> FromIterator<(String, F)> for UnknownFields<F> {
+    fn from_iter<I: IntoIterator<Item = (String, F)>>(iter: I) -> Self {
+        let mut fields = Self::new();
+        for (k, v) in iter {
+            fields.insert(k, v);
+        }
+
+        fields
+    }
+}
+
+impl<F, G> MapFallback<F, G> for UnknownFields<F> {
+    type Mapped = UnknownFields<G>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        let positions = self.1;
+        self.0
+            .into_iter()
+            .map(|(k, v)| {
+                let v = f(v).map_err(|e| e.within(Segment::field(k.clone())))?;
+                Ok((k, v))
+            })
+            .collect::<Result<_, _>>()
+            .map(|fields| UnknownFields(fields, positions))
+    }
+}
+
+impl<F: Serialize> Serialize for UnknownFields<F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (k, v) in &self.0 {
+            map.serialize_entry(k, v)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de, F: Deserialize<'de>> Deserialize<'de> for UnknownFields<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldsVisitor<F>(PhantomData<F>);
+
+        impl<'de, F: Deserialize<'de>> Visitor<'de> for FieldsVisitor<F> {
+            type Value = UnknownFields<F>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of unknown fields")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut fields = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(entry) = map.next_entry()? {
+                    fields.push(entry);
+                }
+
+                Ok(UnknownFields(fields, vec![]))
+            }
+        }
+
+        deserializer.deserialize_map(FieldsVisitor(PhantomData))
+    }
+}
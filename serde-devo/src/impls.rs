@@ -0,0 +1,949 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, VecDeque},
+    ffi::{CString, OsString},
+    hash::{BuildHasher, Hash},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+    path::PathBuf,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+
+use crate::{
+    __private::evolve_within, key, Devolve, Error, Evolve, MapFallback, Segment, TranscodeError,
+};
+
+/// Implements [`Devolve`], [`Evolve`] and [`MapFallback`] for types which hold no unknown
+/// content, devolving them as themselves for any fallback type.
+///
+/// This is implemented for primitives, `String` and common standard library types, and is
+/// needed for any other such type within a `#[devo]` field, like the error type of a
+/// `Result`, which does not derive `Devolve`:
+///
+/// ```
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// pub struct Timeout {
+///     pub secs: u32,
+/// }
+///
+/// serde_devo::identity_impls!(Timeout);
+/// ```
+#[macro_export]
+macro_rules! identity_impls {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl<F> $crate::Devolve<F> for $ty {
+                type Devolved = Self;
+
+                fn into_devolved(self) -> Self::Devolved {
+                    self
+                }
+            }
+
+            impl<F> $crate::Evolve<F> for $ty {
+                type Evolved = Self;
+
+                fn try_into_evolved(self) -> ::std::result::Result<Self::Evolved, $crate::Error> {
+                    ::std::result::Result::Ok(self)
+                }
+            }
+
+            impl<F, G> $crate::MapFallback<F, G> for $ty {
+                type Mapped = Self;
+
+                fn map_fallback<M>(
+                    self,
+                    _: &mut M,
+                ) -> ::std::result::Result<Self::Mapped, $crate::TranscodeError>
+                where
+                    M: FnMut(F) -> ::std::result::Result<G, $crate::TranscodeError>,
+                {
+                    ::std::result::Result::Ok(self)
+                }
+            }
+        )+
+    };
+}
+
+identity_impls! {
+    (), bool, char, String,
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    Duration, SystemTime, PathBuf, OsString, CString,
+    IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6,
+}
+
+macro_rules! tuple_impls {
+    ($(($($n:tt $name:ident)+))+) => {
+        $(
+            impl<F, $($name),+> Devolve<F> for ($($name,)+)
+            where
+                $($name: Devolve<F>,)+
+            {
+                type Devolved = ($($name::Devolved,)+);
+
+                fn into_devolved(self) -> Self::Devolved {
+                    ($(self.$n.into_devolved(),)+)
+                }
+            }
+
+            impl<F, $($name),+> Evolve<F> for ($($name,)+)
+            where
+                $($name: Evolve<F>,)+
+            {
+                type Evolved = ($($name::Evolved,)+);
+
+                fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+                    self.try_into_evolved_with(&mut vec![])
+                }
+
+                fn try_into_evolved_with(
+                    self,
+                    dropped: &mut Vec<Error>,
+                ) -> Result<Self::Evolved, Error> {
+                    Ok(($(
+                        evolve_within::<_, F>(self.$n, dropped, |e| {
+                            e.within(Segment::Index($n))
+                        })?,
+                    )+))
+                }
+
+                fn restore(&mut self, previous: Self) {
+                    $(self.$n.restore(previous.$n);)+
+                }
+
+                fn collect_errors(&self) -> Vec<Error> {
+                    let mut errors = vec![];
+                    $(
+                        errors.extend(
+                            self.$n
+                                .collect_errors()
+                                .into_iter()
+                                .map(|e| e.within(Segment::Index($n))),
+                        );
+                    )+
+                    errors
+                }
+            }
+
+            impl<F, G, $($name),+> MapFallback<F, G> for ($($name,)+)
+            where
+                $($name: MapFallback<F, G>,)+
+            {
+                type Mapped = ($($name::Mapped,)+);
+
+                fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+                where
+                    M: FnMut(F) -> Result<G, TranscodeError>,
+                {
+                    Ok(($(
+                        self.$n
+                            .map_fallback(f)
+                            .map_err(|e| e.within(Segment::Index($n)))?,
+                    )+))
+                }
+            }
+        )+
+    };
+}
+
+tuple_impls! {
+    (0 T0)
+    (0 T0 1 T1)
+    (0 T0 1 T1 2 T2)
+    (0 T0 1 T1 2 T2 3 T3)
+    (0 T0 1 T1 2 T2 3 T3 4 T4)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14 15 T15)
+}
+
+impl<F, T, E> Devolve<F> for Result<T, E>
+where
+    T: Devolve<F>,
+    E: Devolve<F>,
+{
+    type Devolved = Result<T::Devolved, E::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        match self {
+            Ok(t) => Ok(t.into_devolved()),
+            Err(e) => Err(e.into_devolved()),
+        }
+    }
+}
+
+impl<F, T, E> Evolve<F> for Result<T, E>
+where
+    T: Evolve<F>,
+    E: Evolve<F>,
+{
+    type Evolved = Result<T::Evolved, E::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        Ok(match self {
+            Ok(t) => Ok(evolve_within::<_, F>(t, dropped, |e| {
+                e.within(Segment::variant("Ok"))
+            })?),
+            Err(e) => Err(evolve_within::<_, F>(e, dropped, |e| {
+                e.within(Segment::variant("Err"))
+            })?),
+        })
+    }
+
+    fn restore(&mut self, previous: Self) {
+        match (self, previous) {
+            (Ok(t), Ok(previous)) => t.restore(previous),
+            (Err(e), Err(previous)) => e.restore(previous),
+            _ => {}
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        let (errors, variant) = match self {
+            Ok(t) => (t.collect_errors(), "Ok"),
+            Err(e) => (e.collect_errors(), "Err"),
+        };
+
+        errors
+            .into_iter()
+            .map(|e| e.within(Segment::variant(variant)))
+            .collect()
+    }
+}
+
+impl<F, G, T, E> MapFallback<F, G> for Result<T, E>
+where
+    T: MapFallback<F, G>,
+    E: MapFallback<F, G>,
+{
+    type Mapped = Result<T::Mapped, E::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        Ok(match self {
+            Ok(t) => Ok(t
+                .map_fallback(f)
+                .map_err(|e| e.within(Segment::variant("Ok")))?),
+            Err(e) => Err(e
+                .map_fallback(f)
+                .map_err(|e| e.within(Segment::variant("Err")))?),
+        })
+    }
+}
+
+impl<F, T> Devolve<F> for Option<T>
+where
+    T: Devolve<F>,
+{
+    type Devolved = Option<T::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        self.map(Devolve::into_devolved)
+    }
+}
+
+impl<F, T> Evolve<F> for Option<T>
+where
+    T: Evolve<F>,
+{
+    type Evolved = Option<T::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        self.map(|t| t.try_into_evolved_with(dropped)).transpose()
+    }
+
+    fn restore(&mut self, previous: Self) {
+        if let (Some(t), Some(previous)) = (self, previous) {
+            t.restore(previous);
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        self.as_ref().map_or_else(Vec::new, Evolve::collect_errors)
+    }
+}
+
+impl<F, G, T> MapFallback<F, G> for Option<T>
+where
+    T: MapFallback<F, G>,
+{
+    type Mapped = Option<T::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        self.map(|t| t.map_fallback(f)).transpose()
+    }
+}
+
+impl<F, T> Devolve<F> for Box<T>
+where
+    T: Devolve<F>,
+{
+    type Devolved = Box<T::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        Box::new((*self).into_devolved())
+    }
+}
+
+impl<F, T> Evolve<F> for Box<T>
+where
+    T: Evolve<F>,
+{
+    type Evolved = Box<T::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        (*self).try_into_evolved_with(dropped).map(Box::new)
+    }
+
+    fn restore(&mut self, previous: Self) {
+        (**self).restore(*previous);
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        (**self).collect_errors()
+    }
+}
+
+impl<F, G, T> MapFallback<F, G> for Box<T>
+where
+    T: MapFallback<F, G>,
+{
+    type Mapped = Box<T::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        (*self).map_fallback(f).map(Box::new)
+    }
+}
+
+// Shared pointers are unwrapped when uniquely owned, and their contents cloned otherwise.
+impl<F, T> Devolve<F> for Rc<T>
+where
+    T: Devolve<F> + Clone,
+    T::Devolved: Clone,
+{
+    type Devolved = Rc<T::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        Rc::new(Rc::unwrap_or_clone(self).into_devolved())
+    }
+}
+
+impl<F, T> Evolve<F> for Rc<T>
+where
+    T: Evolve<F> + Clone,
+    T::Evolved: Clone,
+{
+    type Evolved = Rc<T::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        Rc::unwrap_or_clone(self)
+            .try_into_evolved_with(dropped)
+            .map(Rc::new)
+    }
+
+    fn restore(&mut self, previous: Self) {
+        Rc::make_mut(self).restore(Rc::unwrap_or_clone(previous));
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        (**self).collect_errors()
+    }
+}
+
+impl<F, G, T> MapFallback<F, G> for Rc<T>
+where
+    T: MapFallback<F, G> + Clone,
+{
+    type Mapped = Rc<T::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        Rc::unwrap_or_clone(self).map_fallback(f).map(Rc::new)
+    }
+}
+
+impl<F, T> Devolve<F> for Arc<T>
+where
+    T: Devolve<F> + Clone,
+    T::Devolved: Clone,
+{
+    type Devolved = Arc<T::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        Arc::new(Arc::unwrap_or_clone(self).into_devolved())
+    }
+}
+
+impl<F, T> Evolve<F> for Arc<T>
+where
+    T: Evolve<F> + Clone,
+    T::Evolved: Clone,
+{
+    type Evolved = Arc<T::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        Arc::unwrap_or_clone(self)
+            .try_into_evolved_with(dropped)
+            .map(Arc::new)
+    }
+
+    fn restore(&mut self, previous: Self) {
+        Arc::make_mut(self).restore(Arc::unwrap_or_clone(previous));
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        (**self).collect_errors()
+    }
+}
+
+impl<F, G, T> MapFallback<F, G> for Arc<T>
+where
+    T: MapFallback<F, G> + Clone,
+{
+    type Mapped = Arc<T::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        Arc::unwrap_or_clone(self).map_fallback(f).map(Arc::new)
+    }
+}
+
+impl<'a, F, T> Devolve<F> for Cow<'a, T>
+where
+    T: Devolve<F> + Clone + 'a,
+    T::Devolved: Clone + 'a,
+{
+    type Devolved = Cow<'a, T::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        Cow::Owned(self.into_owned().into_devolved())
+    }
+}
+
+impl<'a, F, T> Evolve<F> for Cow<'a, T>
+where
+    T: Evolve<F> + Clone + 'a,
+    T::Evolved: Clone + 'a,
+{
+    type Evolved = Cow<'a, T::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        self.into_owned()
+            .try_into_evolved_with(dropped)
+            .map(Cow::Owned)
+    }
+
+    fn restore(&mut self, previous: Self) {
+        self.to_mut().restore(previous.into_owned());
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        (**self).collect_errors()
+    }
+}
+
+impl<'a, F, G, T> MapFallback<F, G> for Cow<'a, T>
+where
+    T: MapFallback<F, G> + Clone + 'a,
+    T::Mapped: Clone + 'a,
+{
+    type Mapped = Cow<'a, T::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        self.into_owned().map_fallback(f).map(Cow::Owned)
+    }
+}
+
+impl<F, T> Devolve<F> for Vec<T>
+where
+    T: Devolve<F>,
+{
+    type Devolved = Vec<T::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        self.into_iter().map(Devolve::into_devolved).collect()
+    }
+}
+
+impl<F, T> Evolve<F> for Vec<T>
+where
+    T: Evolve<F>,
+{
+    type Evolved = Vec<T::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        self.into_iter()
+            .enumerate()
+            .map(|(i, t)| evolve_within::<_, F>(t, dropped, |e| e.within(Segment::Index(i))))
+            .collect()
+    }
+
+    fn restore(&mut self, previous: Self) {
+        for (t, previous) in self.iter_mut().zip(previous) {
+            t.restore(previous);
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        self.iter()
+            .enumerate()
+            .flat_map(|(i, t)| {
+                t.collect_errors()
+                    .into_iter()
+                    .map(move |e| e.within(Segment::Index(i)))
+            })
+            .collect()
+    }
+}
+
+impl<F, G, T> MapFallback<F, G> for Vec<T>
+where
+    T: MapFallback<F, G>,
+{
+    type Mapped = Vec<T::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        self.into_iter()
+            .enumerate()
+            .map(|(i, t)| t.map_fallback(f).map_err(|e| e.within(Segment::Index(i))))
+            .collect()
+    }
+}
+
+impl<F, T> Devolve<F> for VecDeque<T>
+where
+    T: Devolve<F>,
+{
+    type Devolved = VecDeque<T::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        self.into_iter().map(Devolve::into_devolved).collect()
+    }
+}
+
+impl<F, T> Evolve<F> for VecDeque<T>
+where
+    T: Evolve<F>,
+{
+    type Evolved = VecDeque<T::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        self.into_iter()
+            .enumerate()
+            .map(|(i, t)| evolve_within::<_, F>(t, dropped, |e| e.within(Segment::Index(i))))
+            .collect()
+    }
+
+    fn restore(&mut self, previous: Self) {
+        for (t, previous) in self.iter_mut().zip(previous) {
+            t.restore(previous);
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        self.iter()
+            .enumerate()
+            .flat_map(|(i, t)| {
+                t.collect_errors()
+                    .into_iter()
+                    .map(move |e| e.within(Segment::Index(i)))
+            })
+            .collect()
+    }
+}
+
+impl<F, G, T> MapFallback<F, G> for VecDeque<T>
+where
+    T: MapFallback<F, G>,
+{
+    type Mapped = VecDeque<T::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        self.into_iter()
+            .enumerate()
+            .map(|(i, t)| t.map_fallback(f).map_err(|e| e.within(Segment::Index(i))))
+            .collect()
+    }
+}
+
+impl<F, T, const N: usize> Devolve<F> for [T; N]
+where
+    T: Devolve<F>,
+{
+    type Devolved = [T::Devolved; N];
+
+    fn into_devolved(self) -> Self::Devolved {
+        self.map(Devolve::into_devolved)
+    }
+}
+
+impl<F, T, const N: usize> Evolve<F> for [T; N]
+where
+    T: Evolve<F>,
+{
+    type Evolved = [T::Evolved; N];
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        let evolved = self
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| evolve_within::<_, F>(t, dropped, |e| e.within(Segment::Index(i))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(evolved
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("array length is preserved")))
+    }
+
+    fn restore(&mut self, previous: Self) {
+        for (t, previous) in self.iter_mut().zip(previous) {
+            t.restore(previous);
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        self.iter()
+            .enumerate()
+            .flat_map(|(i, t)| {
+                t.collect_errors()
+                    .into_iter()
+                    .map(move |e| e.within(Segment::Index(i)))
+            })
+            .collect()
+    }
+}
+
+impl<F, G, T, const N: usize> MapFallback<F, G> for [T; N]
+where
+    T: MapFallback<F, G>,
+{
+    type Mapped = [T::Mapped; N];
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        let mapped = self
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| t.map_fallback(f).map_err(|e| e.within(Segment::Index(i))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(mapped
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("array length is preserved")))
+    }
+}
+
+impl<F, K, V, S> Devolve<F> for HashMap<K, V, S>
+where
+    K: Eq + Hash + Serialize,
+    V: Devolve<F>,
+    S: BuildHasher + Default,
+{
+    type Devolved = HashMap<K, V::Devolved, S>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        self.into_iter()
+            .map(|(k, v)| (k, v.into_devolved()))
+            .collect()
+    }
+}
+
+impl<F, K, V, S> Evolve<F> for HashMap<K, V, S>
+where
+    K: Eq + Hash + Serialize,
+    V: Evolve<F>,
+    S: BuildHasher + Default,
+{
+    type Evolved = HashMap<K, V::Evolved, S>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        self.into_iter()
+            .map(|(k, v)| {
+                let extend = |e: Error| e.within(Segment::Key(key::to_string(&k)));
+                let v = evolve_within::<_, F>(v, dropped, extend)?;
+                Ok((k, v))
+            })
+            .collect()
+    }
+
+    fn restore(&mut self, previous: Self) {
+        for (k, previous) in previous {
+            if let Some(v) = self.get_mut(&k) {
+                v.restore(previous);
+            }
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        self.iter()
+            .flat_map(|(k, v)| {
+                v.collect_errors()
+                    .into_iter()
+                    .map(|e| e.within(Segment::Key(key::to_string(k))))
+            })
+            .collect()
+    }
+}
+
+impl<F, G, K, V, S> MapFallback<F, G> for HashMap<K, V, S>
+where
+    K: Eq + Hash + Serialize,
+    V: MapFallback<F, G>,
+    S: BuildHasher + Default,
+{
+    type Mapped = HashMap<K, V::Mapped, S>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        self.into_iter()
+            .map(|(k, v)| {
+                let v = v
+                    .map_fallback(f)
+                    .map_err(|e| e.within(Segment::Key(key::to_string(&k))))?;
+                Ok((k, v))
+            })
+            .collect()
+    }
+}
+
+impl<F, K, V> Devolve<F> for BTreeMap<K, V>
+where
+    K: Ord + Serialize,
+    V: Devolve<F>,
+{
+    type Devolved = BTreeMap<K, V::Devolved>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        self.into_iter()
+            .map(|(k, v)| (k, v.into_devolved()))
+            .collect()
+    }
+}
+
+impl<F, K, V> Evolve<F> for BTreeMap<K, V>
+where
+    K: Ord + Serialize,
+    V: Evolve<F>,
+{
+    type Evolved = BTreeMap<K, V::Evolved>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        self.into_iter()
+            .map(|(k, v)| {
+                let extend = |e: Error| e.within(Segment::Key(key::to_string(&k)));
+                let v = evolve_within::<_, F>(v, dropped, extend)?;
+                Ok((k, v))
+            })
+            .collect()
+    }
+
+    fn restore(&mut self, previous: Self) {
+        for (k, previous) in previous {
+            if let Some(v) = self.get_mut(&k) {
+                v.restore(previous);
+            }
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        self.iter()
+            .flat_map(|(k, v)| {
+                v.collect_errors()
+                    .into_iter()
+                    .map(|e| e.within(Segment::Key(key::to_string(k))))
+            })
+            .collect()
+    }
+}
+
+impl<F, G, K, V> MapFallback<F, G> for BTreeMap<K, V>
+where
+    K: Ord + Serialize,
+    V: MapFallback<F, G>,
+{
+    type Mapped = BTreeMap<K, V::Mapped>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        self.into_iter()
+            .map(|(k, v)| {
+                let v = v
+                    .map_fallback(f)
+                    .map_err(|e| e.within(Segment::Key(key::to_string(&k))))?;
+                Ok((k, v))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<F, K, V, S> Devolve<F> for indexmap::IndexMap<K, V, S>
+where
+    K: Eq + Hash + Serialize,
+    V: Devolve<F>,
+    S: BuildHasher + Default,
+{
+    type Devolved = indexmap::IndexMap<K, V::Devolved, S>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        self.into_iter()
+            .map(|(k, v)| (k, v.into_devolved()))
+            .collect()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<F, K, V, S> Evolve<F> for indexmap::IndexMap<K, V, S>
+where
+    K: Eq + Hash + Serialize,
+    V: Evolve<F>,
+    S: BuildHasher + Default,
+{
+    type Evolved = indexmap::IndexMap<K, V::Evolved, S>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        self.into_iter()
+            .map(|(k, v)| {
+                let extend = |e: Error| e.within(Segment::Key(key::to_string(&k)));
+                let v = evolve_within::<_, F>(v, dropped, extend)?;
+                Ok((k, v))
+            })
+            .collect()
+    }
+
+    fn restore(&mut self, previous: Self) {
+        for (k, previous) in previous {
+            if let Some(v) = self.get_mut(&k) {
+                v.restore(previous);
+            }
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        self.iter()
+            .flat_map(|(k, v)| {
+                v.collect_errors()
+                    .into_iter()
+                    .map(|e| e.within(Segment::Key(key::to_string(k))))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<F, G, K, V, S> MapFallback<F, G> for indexmap::IndexMap<K, V, S>
+where
+    K: Eq + Hash + Serialize,
+    V: MapFallback<F, G>,
+    S: BuildHasher + Default,
+{
+    type Mapped = indexmap::IndexMap<K, V::Mapped, S>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        self.into_iter()
+            .map(|(k, v)| {
+                let v = v
+                    .map_fallback(f)
+                    .map_err(|e| e.within(Segment::Key(key::to_string(&k))))?;
+                Ok((k, v))
+            })
+            .collect()
+    }
+}
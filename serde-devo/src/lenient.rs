@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Devolve, Error, Evolve, MapFallback, TranscodeError};
+
+/// A value which is either deserialized as `T`, or else kept as the fallback type `F`
+/// when it does not match, such as a foreign type or a field whose type has changed.
+///
+/// Serialized as whichever value it holds. Generated for fields with the
+/// `#[devo(lenient)]` attribute, as the type of the field in the devolved type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Lenient<T, F> {
+    Known(T),
+    Unknown(F),
+}
+
+impl<T, F> Lenient<T, F> {
+    pub fn known(&self) -> Option<&T> {
+        match self {
+            Self::Known(t) => Some(t),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    pub fn unknown(&self) -> Option<&F> {
+        match self {
+            Self::Known(_) => None,
+            Self::Unknown(f) => Some(f),
+        }
+    }
+
+    pub fn into_known(self) -> Option<T> {
+        match self {
+            Self::Known(t) => Some(t),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+impl<T, F> From<T> for Lenient<T, F> {
+    fn from(t: T) -> Self {
+        Self::Known(t)
+    }
+}
+
+// Used directly within an evolved type, unknown values are kept rather than failing.
+impl<F, T> Devolve<F> for Lenient<T, F>
+where
+    T: Devolve<F>,
+{
+    type Devolved = Lenient<T::Devolved, F>;
+
+    fn into_devolved(self) -> Self::Devolved {
+        match self {
+            Self::Known(t) => Lenient::Known(t.into_devolved()),
+            Self::Unknown(f) => Lenient::Unknown(f),
+        }
+    }
+}
+
+impl<F, T> Evolve<F> for Lenient<T, F>
+where
+    T: Evolve<F>,
+{
+    type Evolved = Lenient<T::Evolved, F>;
+
+    fn try_into_evolved(self) -> Result<Self::Evolved, Error> {
+        self.try_into_evolved_with(&mut vec![])
+    }
+
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        Ok(match self {
+            Self::Known(t) => Lenient::Known(t.try_into_evolved_with(dropped)?),
+            Self::Unknown(f) => Lenient::Unknown(f),
+        })
+    }
+
+    fn restore(&mut self, previous: Self) {
+        if let (Self::Known(t), Self::Known(previous)) = (self, previous) {
+            t.restore(previous);
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<Error> {
+        self.known().map_or_else(Vec::new, Evolve::collect_errors)
+    }
+}
+
+impl<F, G, T> MapFallback<F, G> for Lenient<T, F>
+where
+    T: MapFallback<F, G>,
+{
+    type Mapped = Lenient<T::Mapped, G>;
+
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>,
+    {
+        Ok(match self {
+            Self::Known(t) => Lenient::Known(t.map_fallback(f)?),
+            Self::Unknown(v) => Lenient::Unknown(f(v)?),
+        })
+    }
+}
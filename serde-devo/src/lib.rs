@@ -1,58 +1,437 @@
-use std::fmt;
+use std::{borrow::Cow, fmt};
 
 extern crate serde_devo_derive;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 pub use serde_devo_derive::Devolve;
 
-#[derive(Debug, Clone, Deserialize)]
+mod fallback;
+mod fields;
+mod impls;
+mod key;
+mod lenient;
+mod path;
+mod preserved;
+mod tagged;
+mod transcode;
+mod value;
+
+pub use fallback::Fallback;
+pub use fields::UnknownFields;
+pub use lenient::Lenient;
+pub use path::Segment;
+pub use preserved::Preserved;
+pub use transcode::{transcode, MapFallback, TranscodeError};
+pub use value::Value;
+
+/// The reason a devolved value could not be evolved.
+///
+/// Serialized as an internally tagged map, e.g.
+/// `{"kind":"unknown_variant","ty":"Tank","path":[{"field":"fishes"},{"index":1}],"payload":"BlueFish"}`,
+/// with the path listed outermost segment first and the payload serialized as the content
+/// it was captured from. The `path` of each variant is held innermost segment first, the
+/// order in which it is recorded as the error unwinds, and [`Error::path`] reverses it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum Error {
+    /// The content had a variant tag which this version of the enum does not know.
     UnknownVariant {
-        #[serde(borrow)]
-        ty: &'static str,
-        #[serde(borrow)]
-        path: Vec<&'static str>,
+        ty: Cow<'static, str>,
+        #[serde(with = "path::outermost_first")]
+        path: Vec<Segment>,
+        tag: Option<String>,
+        payload: Value,
+    },
+    /// The content was tagged as a known variant, but could not be deserialized as one.
+    MalformedVariant {
+        ty: Cow<'static, str>,
+        #[serde(with = "path::outermost_first")]
+        path: Vec<Segment>,
+        tag: String,
+        payload: Value,
+    },
+    /// The content of a [`Lenient`] value could not be deserialized as the expected type.
+    UnknownValue {
+        ty: Cow<'static, str>,
+        #[serde(with = "path::outermost_first")]
+        path: Vec<Segment>,
+        payload: Value,
     },
 }
 impl std::error::Error for Error {}
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "evolution failed: {}.", self.ty())?;
+        for segment in self.segments().iter().rev() {
+            write!(f, "{segment}.")?;
+        }
         match self {
-            Self::UnknownVariant { ty, path } => {
-                write!(
-                    f,
-                    "evolution failed: {ty}.{}",
-                    path.iter().fold("[unknown_variant]".to_string(), |a, b| {
-                        format!("{b}.{a}")
-                    })
-                )
-            }
+            Self::UnknownVariant { .. } => f.write_str("[unknown_variant]"),
+            Self::MalformedVariant { tag, .. } => write!(f, "[malformed_variant:{tag}]"),
+            Self::UnknownValue { .. } => f.write_str("[unknown_value]"),
         }
     }
 }
 
 impl Error {
-    pub fn extend(self, ty: &'static str, ext: &'static str) -> Self {
+    /// An unrecognized variant of the enum `ty`, carrying its tag where one could be
+    /// found and the fallback value it was deserialized as.
+    pub fn unknown_variant(
+        ty: &'static str,
+        tag: Option<String>,
+        payload: &impl Serialize,
+    ) -> Self {
+        Self::UnknownVariant {
+            ty: Cow::Borrowed(ty),
+            path: vec![],
+            tag,
+            payload: Self::captured(payload),
+        }
+    }
+
+    /// A known variant `tag` of the enum `ty` whose content could not be deserialized,
+    /// carrying the fallback value it was deserialized as instead.
+    pub fn malformed_variant(
+        ty: &'static str,
+        tag: impl Into<String>,
+        payload: &impl Serialize,
+    ) -> Self {
+        Self::MalformedVariant {
+            ty: Cow::Borrowed(ty),
+            path: vec![],
+            tag: tag.into(),
+            payload: Self::captured(payload),
+        }
+    }
+
+    /// A value of type `ty` which could not be deserialized, carrying the fallback value it
+    /// was deserialized as instead.
+    pub fn unknown_value(ty: &'static str, payload: &impl Serialize) -> Self {
+        Self::UnknownValue {
+            ty: Cow::Borrowed(ty),
+            path: vec![],
+            payload: Self::captured(payload),
+        }
+    }
+
+    /// The fallback value as a [`Value`], or the reason it could not be serialized as one,
+    /// which fallback values deserialized from self-describing content do not fail to be.
+    fn captured(payload: &impl Serialize) -> Value {
+        Value::serialized(payload).unwrap_or_else(|e| Value::String(e.to_string()))
+    }
+
+    /// Records that the failure occurred within the derived type `ty`, at the given segment
+    /// of its serialized form. Content which is serialized inline, such as a flattened
+    /// field, has no segment of its own.
+    pub fn extend(mut self, ty: &'static str, segment: impl Into<Option<Segment>>) -> Self {
+        match &mut self {
+            Self::UnknownVariant { ty: t, .. }
+            | Self::MalformedVariant { ty: t, .. }
+            | Self::UnknownValue { ty: t, .. } => *t = Cow::Borrowed(ty),
+        }
+
+        match segment.into() {
+            Some(segment) => self.within(segment),
+            None => self,
+        }
+    }
+
+    /// Records that the failure occurred at the given segment of a container, such as an
+    /// element of a `Vec`, leaving it attributed to the type the container is held by.
+    pub fn within(mut self, segment: Segment) -> Self {
+        match &mut self {
+            Self::UnknownVariant { path, .. }
+            | Self::MalformedVariant { path, .. }
+            | Self::UnknownValue { path, .. } => path.push(segment),
+        }
+
+        self
+    }
+
+    /// The name of the outermost derived type which failed to evolve, or of the type which
+    /// failed to evolve if it is not held by one.
+    pub fn ty(&self) -> &str {
         match self {
-            Self::UnknownVariant { mut path, .. } => {
-                path.push(ext);
-                Self::UnknownVariant { ty, path }
+            Self::UnknownVariant { ty, .. }
+            | Self::MalformedVariant { ty, .. }
+            | Self::UnknownValue { ty, .. } => ty,
+        }
+    }
+
+    /// The path to the unrecognized content, outermost segment first.
+    pub fn path(&self) -> Vec<Segment> {
+        self.segments().iter().rev().cloned().collect()
+    }
+
+    /// Renders the path to the unrecognized content as an RFC 6901 JSON Pointer,
+    /// e.g. `/fishes/1`.
+    pub fn pointer(&self) -> String {
+        path::to_pointer(self.segments())
+    }
+
+    /// The path to the unrecognized content, innermost segment first.
+    fn segments(&self) -> &[Segment] {
+        match self {
+            Self::UnknownVariant { path, .. }
+            | Self::MalformedVariant { path, .. }
+            | Self::UnknownValue { path, .. } => path,
+        }
+    }
+
+    /// The variant tag of the unrecognized content, if it had one.
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            Self::UnknownVariant { tag, .. } => tag.as_deref(),
+            Self::MalformedVariant { tag, .. } => Some(tag),
+            Self::UnknownValue { .. } => None,
+        }
+    }
+
+    /// The unrecognized content which caused evolution to fail, which serializes as the
+    /// content it was captured from and can be deserialized as the fallback type again with
+    /// [`Value::deserialized`].
+    pub fn payload(&self) -> &Value {
+        match self {
+            Self::UnknownVariant { payload, .. }
+            | Self::MalformedVariant { payload, .. }
+            | Self::UnknownValue { payload, .. } => payload,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Error, Evolve, Fallback, Lenient, Segment, TranscodeError, UnknownFields};
+
+    /// Asserts that the fallback type named by a derived container implements `Fallback`.
+    pub fn require_fallback<F: Fallback>() {}
+
+    /// Deserializes the fallback value of a devolved enum, succeeding only when it is
+    /// tagged as one of the enum's known variants.
+    pub fn deserialize_malformed<'de, D, F>(
+        deserializer: D,
+        tags: &[&str],
+        tag_key: Option<&str>,
+    ) -> Result<(String, F), D::Error>
+    where
+        D: Deserializer<'de>,
+        F: Deserialize<'de> + Serialize,
+    {
+        let content = F::deserialize(deserializer)?;
+        match find_tag(&content, tag_key) {
+            Some(tag) if tags.contains(&tag.as_str()) => Ok((tag, content)),
+            _ => Err(de::Error::custom("not a known variant")),
+        }
+    }
+
+    /// Evolves a value nested within another, extending the path of its error and of
+    /// anything dropped within it.
+    pub fn evolve_within<D, F>(
+        devolved: D,
+        dropped: &mut Vec<Error>,
+        extend: impl Fn(Error) -> Error,
+    ) -> Result<D::Evolved, Error>
+    where
+        D: Evolve<F>,
+    {
+        let len = dropped.len();
+        match devolved.try_into_evolved_with(dropped) {
+            Ok(evolved) => {
+                let within = dropped.drain(len..).map(&extend).collect::<Vec<_>>();
+                dropped.extend(within);
+                Ok(evolved)
+            }
+            Err(e) => {
+                // Anything dropped within a value which failed to evolve is superseded by
+                // the failure itself.
+                dropped.truncate(len);
+                Err(extend(e))
             }
         }
     }
+
+    /// Evolves a field, replacing it with `fallback` and recording the reason in `dropped`
+    /// if it contains unknown content.
+    pub fn evolve_or<D, F>(
+        devolved: D,
+        dropped: &mut Vec<Error>,
+        fallback: impl FnOnce() -> D::Evolved,
+        extend: impl Fn(Error) -> Error,
+    ) -> D::Evolved
+    where
+        D: Evolve<F>,
+    {
+        evolve_within(devolved, dropped, extend).unwrap_or_else(|e| {
+            dropped.push(e);
+            fallback()
+        })
+    }
+
+    /// Evolves the elements of a sequence, leaving out and recording in `dropped` those
+    /// which contain unknown content.
+    pub fn evolve_skipping<I, C, F>(
+        devolved: I,
+        dropped: &mut Vec<Error>,
+        extend: impl Fn(Error) -> Error,
+    ) -> C
+    where
+        I: IntoIterator,
+        I::Item: Evolve<F>,
+        C: FromIterator<<I::Item as Evolve<F>>::Evolved>,
+    {
+        devolved
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, element)| {
+                let extend = |e: Error| extend(e.within(Segment::Index(i)));
+                match evolve_within(element, dropped, extend) {
+                    Ok(evolved) => Some(evolved),
+                    Err(e) => {
+                        dropped.push(e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Restores a sequence evolved with the "skip" policy, putting each element which was
+    /// left out back at its original position and restoring the others in order.
+    pub fn restore_skipping<C, F>(current: &mut C, previous: C)
+    where
+        C: Default + IntoIterator + FromIterator<C::Item>,
+        C::Item: Evolve<F>,
+    {
+        let mut evolved = std::mem::take(current).into_iter();
+        let mut restored = vec![];
+        for previous in previous {
+            if !previous.collect_errors().is_empty() {
+                restored.push(previous);
+            } else if let Some(mut element) = evolved.next() {
+                element.restore(previous);
+                restored.push(element);
+            }
+        }
+        restored.extend(evolved);
+
+        *current = restored.into_iter().collect();
+    }
+
+    /// Evolves a lenient field as the value it holds, failing if that could not be
+    /// deserialized.
+    pub fn evolve_lenient<T, F: Serialize>(lenient: Lenient<T, F>) -> Result<T, Error> {
+        match lenient {
+            Lenient::Known(t) => Ok(t),
+            Lenient::Unknown(f) => Err(Error::unknown_value(std::any::type_name::<T>(), &f)),
+        }
+    }
+
+    /// The error which evolving a lenient field would produce, if any.
+    pub fn lenient_error<T, F: Serialize>(lenient: &Lenient<T, F>) -> Option<Error> {
+        lenient
+            .unknown()
+            .map(|f| Error::unknown_value(std::any::type_name::<T>(), f))
+    }
+
+    /// Converts the fallback content of a lenient field, whose known value holds no
+    /// fallback content of its own.
+    pub fn map_lenient<T, F, G>(
+        lenient: Lenient<T, F>,
+        f: &mut impl FnMut(F) -> Result<G, TranscodeError>,
+    ) -> Result<Lenient<T, G>, TranscodeError> {
+        Ok(match lenient {
+            Lenient::Known(t) => Lenient::Known(t),
+            Lenient::Unknown(v) => Lenient::Unknown(f(v)?),
+        })
+    }
+
+    /// Finds the variant tag of a fallback value of any type, as `Fallback::variant_tag`
+    /// finds it in the value's `Value` form.
+    pub fn find_tag<F: Serialize>(content: &F, tag_key: Option<&str>) -> Option<String> {
+        let value = crate::Value::serialized(content).ok()?;
+        crate::Fallback::variant_tag(&value, tag_key).map(str::to_owned)
+    }
+
+    /// Deserializes an unrecognized variant of an internally tagged enum as its tag and
+    /// the remaining fields.
+    pub fn deserialize_internal<'de, D, F>(
+        deserializer: D,
+        tag_key: &str,
+    ) -> Result<(String, UnknownFields<F>), D::Error>
+    where
+        D: Deserializer<'de>,
+        F: Deserialize<'de>,
+    {
+        crate::tagged::deserialize_internal(deserializer, tag_key)
+    }
+
+    /// Serializes an unrecognized variant of an internally tagged enum as it was received.
+    pub fn serialize_internal<S: Serializer, F: Serialize>(
+        tag: &str,
+        content: &UnknownFields<F>,
+        tag_key: &str,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        crate::tagged::serialize_internal(tag, content, tag_key, serializer)
+    }
+
+    /// Deserializes an unrecognized variant of an adjacently tagged enum as its tag,
+    /// content, if any, and other entries.
+    pub fn deserialize_adjacent<'de, D, F>(
+        deserializer: D,
+        tag_key: &str,
+        content_key: &str,
+    ) -> Result<(String, Option<F>, UnknownFields<F>), D::Error>
+    where
+        D: Deserializer<'de>,
+        F: Deserialize<'de>,
+    {
+        crate::tagged::deserialize_adjacent(deserializer, tag_key, content_key)
+    }
+
+    /// Serializes an unrecognized variant of an adjacently tagged enum as it was received.
+    pub fn serialize_adjacent<S: Serializer, F: Serialize>(
+        tag: &str,
+        content: Option<&F>,
+        unknown_fields: &UnknownFields<F>,
+        tag_key: &str,
+        content_key: &str,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        crate::tagged::serialize_adjacent(
+            tag,
+            content,
+            unknown_fields,
+            tag_key,
+            content_key,
+            serializer,
+        )
+    }
 }
 
-/// A **data structure** which represents the complete, or latest known form of another
-/// devolved **data structure**, and which may be converted into this "devolved" form.
-///
-/// Additionally, serde-devo provides a procedural macro called [`serde_devo_derive`]
-/// to automatically implement `Devolvable` for structs and enums in your program.
-///
-/// In rare cases it may be necessary to implement `Devolvable` manually for some
-/// type in your program.
-#[cfg(feature = "json")]
-pub trait Devolve<T = serde_json::Value> {
-    type Devolved: Evolve<T, Evolved = Self>;
-    fn into_devolved(self) -> Self::Devolved;
+/// The error returned by [`Evolve::try_evolve`], which hands back the devolved value that
+/// could not be evolved alongside the reason.
+#[derive(Debug, Clone)]
+pub struct EvolveError<D> {
+    pub devolved: D,
+    pub error: Error,
+}
+impl<D: fmt::Debug> std::error::Error for EvolveError<D> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+impl<D> fmt::Display for EvolveError<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<D> From<EvolveError<D>> for Error {
+    fn from(e: EvolveError<D>) -> Self {
+        e.error
+    }
 }
 
 /// A **data structure** which represents the complete, or latest known form of another
@@ -63,8 +442,10 @@ pub trait Devolve<T = serde_json::Value> {
 ///
 /// In rare cases it may be necessary to implement `Devolvable` manually for some
 /// type in your program.
-#[cfg(not(feature = "json"))]
-pub trait Devolve<T> {
+///
+/// The fallback type defaults to the format-agnostic [`Value`] regardless of the enabled
+/// features, so that enabling a format feature never changes this trait's signature.
+pub trait Devolve<T = Value> {
     type Devolved: Evolve<T, Evolved = Self>;
     fn into_devolved(self) -> Self::Devolved;
 }
@@ -81,4 +462,71 @@ pub trait Devolve<T> {
 pub trait Evolve<T>: Sized {
     type Evolved: Devolve<T, Devolved = Self>;
     fn try_into_evolved(self) -> Result<Self::Evolved, Error>;
+
+    /// Evolves this value as [`Evolve::try_into_evolved`] does, recording in `dropped` an
+    /// error for each piece of unknown content discarded by a field's `on_unknown` policy.
+    fn try_into_evolved_with(self, dropped: &mut Vec<Error>) -> Result<Self::Evolved, Error> {
+        let _ = dropped;
+        self.try_into_evolved()
+    }
+
+    /// Evolves this value, returning alongside it an error for each piece of unknown
+    /// content which was discarded rather than preventing evolution.
+    fn try_into_evolved_lossy(self) -> Result<(Self::Evolved, Vec<Error>), Error> {
+        let mut dropped = vec![];
+        let evolved = self.try_into_evolved_with(&mut dropped)?;
+        Ok((evolved, dropped))
+    }
+
+    /// Walks the whole of this value without consuming it, returning an error for every
+    /// piece of unrecognized content which would prevent it from being evolved.
+    ///
+    /// Returns no errors unless overridden, as is done for every derived type, so manual
+    /// implementations which may fail to evolve should override it for
+    /// [`Evolve::validate`] to be accurate.
+    fn collect_errors(&self) -> Vec<Error> {
+        vec![]
+    }
+
+    /// Checks whether this value could be evolved, returning the error which
+    /// [`Evolve::try_into_evolved`] would produce without consuming the value.
+    fn validate(&self) -> Result<(), Error> {
+        match self.collect_errors().into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Evolves a copy of this value, or returns the original unchanged alongside the error
+    /// if it contains anything unrecognized.
+    fn try_evolve(self) -> Result<Self::Evolved, EvolveError<Self>>
+    where
+        Self: Clone,
+    {
+        let devolved = self.clone();
+        self.try_into_evolved()
+            .map_err(|error| EvolveError { devolved, error })
+    }
+
+    /// Evolves a copy of this value, retaining the original so that its unknown data can
+    /// be restored when the evolved value is devolved again with [`Preserved::into_devolved`].
+    fn try_into_preserved(self) -> Result<Preserved<Self::Evolved, T>, Error>
+    where
+        Self: Clone,
+    {
+        let residue = self.clone();
+        self.try_into_evolved()
+            .map(|evolved| Preserved::new(evolved, residue))
+    }
+
+    /// Re-attaches to this freshly devolved value any unknown data retained by the
+    /// `previous` devolved form of the same value, such as captured unknown fields.
+    ///
+    /// Values are matched up structurally: struct fields by name, sequence elements by
+    /// position, map entries by key and enum variants only where both sides agree.
+    /// Elements left out of a sequence by the `"skip"` policy are put back in place.
+    /// Data in `previous` without a counterpart in `self` is dropped.
+    fn restore(&mut self, previous: Self) {
+        let _ = previous;
+    }
 }
@@ -0,0 +1,71 @@
+use std::{borrow::Cow, fmt};
+
+use serde::{Deserialize, Serialize};
+
+/// A single step along the path to the content which failed to evolve, named as it
+/// appears in the serialized data.
+///
+/// Serialized as a single entry map, e.g. `{"field":"fishes"}` or `{"index":1}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Segment {
+    /// A named field of a struct or struct variant.
+    Field(Cow<'static, str>),
+    /// An element of a sequence, or a positional field of a tuple.
+    Index(usize),
+    /// The value of a map entry, with its key rendered as a string.
+    Key(String),
+    /// The content of an externally tagged enum variant.
+    Variant(Cow<'static, str>),
+}
+
+impl Segment {
+    pub fn field(name: impl Into<Cow<'static, str>>) -> Self {
+        Self::Field(name.into())
+    }
+
+    pub fn variant(name: impl Into<Cow<'static, str>>) -> Self {
+        Self::Variant(name.into())
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Field(name) | Self::Variant(name) => f.write_str(name),
+            Self::Index(i) => write!(f, "{i}"),
+            Self::Key(key) => f.write_str(key),
+        }
+    }
+}
+
+/// Renders a path held innermost segment first as an RFC 6901 JSON Pointer.
+pub(crate) fn to_pointer(path: &[Segment]) -> String {
+    path.iter()
+        .rev()
+        .fold(String::new(), |mut pointer, segment| {
+            pointer.push('/');
+            pointer.push_str(&segment.to_string().replace('~', "~0").replace('/', "~1"));
+            pointer
+        })
+}
+
+/// Serializes a path held innermost segment first, as it is recorded while an error
+/// unwinds, with its outermost segment first.
+pub(crate) mod outermost_first {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Segment;
+
+    pub fn serialize<S: Serializer>(path: &[Segment], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(path.iter().rev())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Segment>, D::Error> {
+        let mut path = Vec::<Segment>::deserialize(deserializer)?;
+        path.reverse();
+        Ok(path)
+    }
+}
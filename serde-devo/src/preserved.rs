@@ -0,0 +1,47 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{Devolve, Evolve};
+
+/// An evolved value which remembers the devolved form it was evolved from, so that any
+/// unknown data it carried may be re-attached when the value is devolved again.
+///
+/// Obtained from [`Evolve::try_into_preserved`], and dereferences to the evolved value
+/// for reading and modification.
+#[derive(Debug, Clone)]
+pub struct Preserved<T: Devolve<F>, F> {
+    evolved: T,
+    residue: T::Devolved,
+}
+
+impl<T: Devolve<F>, F> Preserved<T, F> {
+    pub(crate) fn new(evolved: T, residue: T::Devolved) -> Self {
+        Self { evolved, residue }
+    }
+
+    /// Devolves the (possibly modified) evolved value, restoring the unknown data of the
+    /// devolved form it was originally evolved from.
+    pub fn into_devolved(self) -> T::Devolved {
+        let mut devolved = self.evolved.into_devolved();
+        devolved.restore(self.residue);
+        devolved
+    }
+
+    /// Discards the retained devolved form, returning only the evolved value.
+    pub fn into_inner(self) -> T {
+        self.evolved
+    }
+}
+
+impl<T: Devolve<F>, F> Deref for Preserved<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.evolved
+    }
+}
+
+impl<T: Devolve<F>, F> DerefMut for Preserved<T, F> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.evolved
+    }
+}
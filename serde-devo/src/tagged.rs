@@ -0,0 +1,225 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::UnknownFields;
+
+/// Splits an internally tagged variant into its tag and its remaining entries, in the
+/// order they were received and recording where the tag was among them.
+pub(crate) fn deserialize_internal<'de, D, F>(
+    deserializer: D,
+    tag_key: &str,
+) -> Result<(String, UnknownFields<F>), D::Error>
+where
+    D: Deserializer<'de>,
+    F: Deserialize<'de>,
+{
+    struct InternalVisitor<'a, F> {
+        tag_key: &'a str,
+        marker: PhantomData<F>,
+    }
+
+    impl<'de, F: Deserialize<'de>> Visitor<'de> for InternalVisitor<'_, F> {
+        type Value = (String, UnknownFields<F>);
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a map with a `{}` tag", self.tag_key)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut tag = None;
+            let mut fields = Vec::with_capacity(map.size_hint().unwrap_or_default());
+            while let Some(key) = map.next_key::<String>()? {
+                if key != self.tag_key {
+                    fields.push((key, map.next_value()?));
+                } else if tag.is_some() {
+                    return Err(de::Error::custom(format_args!("duplicate field `{key}`")));
+                } else {
+                    tag = Some((fields.len(), map.next_value()?));
+                }
+            }
+
+            let (position, tag) = tag.ok_or_else(|| {
+                de::Error::custom(format_args!("missing field `{}`", self.tag_key))
+            })?;
+            let positions = match position {
+                0 => vec![],
+                _ => vec![position],
+            };
+            Ok((tag, UnknownFields(fields, positions)))
+        }
+    }
+
+    deserializer.deserialize_map(InternalVisitor {
+        tag_key,
+        marker: PhantomData,
+    })
+}
+
+/// Serializes an internally tagged variant with its tag where it was received among its
+/// remaining entries, or ahead of them.
+pub(crate) fn serialize_internal<S, F>(
+    tag: &str,
+    content: &UnknownFields<F>,
+    tag_key: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    F: Serialize,
+{
+    let position = content.1.first().copied().unwrap_or(0);
+    serialize_entries(
+        content,
+        vec![(position, tag_key, Entry::Tag(tag))],
+        serializer,
+    )
+}
+
+/// Splits an adjacently tagged variant into its tag, its content, which is absent for unit
+/// variants, and any other entries, recording where the tag and content were among them.
+pub(crate) fn deserialize_adjacent<'de, D, F>(
+    deserializer: D,
+    tag_key: &str,
+    content_key: &str,
+) -> Result<(String, Option<F>, UnknownFields<F>), D::Error>
+where
+    D: Deserializer<'de>,
+    F: Deserialize<'de>,
+{
+    struct AdjacentVisitor<'a, F> {
+        tag_key: &'a str,
+        content_key: &'a str,
+        marker: PhantomData<F>,
+    }
+
+    impl<'de, F: Deserialize<'de>> Visitor<'de> for AdjacentVisitor<'_, F> {
+        type Value = (String, Option<F>, UnknownFields<F>);
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "a map with a `{}` tag and optional `{}` content",
+                self.tag_key, self.content_key
+            )
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut tag = None;
+            let mut content = None;
+            let mut fields = vec![];
+            let mut position = 0;
+            while let Some(key) = map.next_key::<String>()? {
+                if key == self.tag_key {
+                    if tag.is_some() {
+                        return Err(de::Error::custom(format_args!("duplicate field `{key}`")));
+                    }
+                    tag = Some((position, map.next_value()?));
+                } else if key == self.content_key {
+                    if content.is_some() {
+                        return Err(de::Error::custom(format_args!("duplicate field `{key}`")));
+                    }
+                    content = Some((position, map.next_value()?));
+                } else {
+                    fields.push((key, map.next_value()?));
+                }
+                position += 1;
+            }
+
+            let (tag_position, tag) = tag.ok_or_else(|| {
+                de::Error::custom(format_args!("missing field `{}`", self.tag_key))
+            })?;
+            let positions = match (tag_position, content.as_ref().map(|(p, _)| *p)) {
+                (0, None | Some(1)) => vec![],
+                (tag_position, None) => vec![tag_position],
+                (tag_position, Some(content_position)) => vec![tag_position, content_position],
+            };
+            let content = content.map(|(_, content)| content);
+            Ok((tag, content, UnknownFields(fields, positions)))
+        }
+    }
+
+    deserializer.deserialize_map(AdjacentVisitor {
+        tag_key,
+        content_key,
+        marker: PhantomData,
+    })
+}
+
+/// Serializes an adjacently tagged variant with its tag and any content where they were
+/// received among its other entries, or as its tag followed by its content.
+pub(crate) fn serialize_adjacent<S, F>(
+    tag: &str,
+    content: Option<&F>,
+    unknown_fields: &UnknownFields<F>,
+    tag_key: &str,
+    content_key: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    F: Serialize,
+{
+    let tag_position = unknown_fields.1.first().copied().unwrap_or(0);
+    let mut split = vec![(tag_position, tag_key, Entry::Tag(tag))];
+    if let Some(content) = content {
+        let position = unknown_fields.1.get(1).copied();
+        split.push((
+            position.unwrap_or(tag_position + 1),
+            content_key,
+            Entry::Content(content),
+        ));
+    }
+
+    serialize_entries(unknown_fields, split, serializer)
+}
+
+/// An entry split off from the other entries of a tagged variant.
+enum Entry<'a, F> {
+    Tag(&'a str),
+    Content(&'a F),
+}
+
+impl<F: Serialize> Serialize for Entry<'_, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Tag(tag) => tag.serialize(serializer),
+            Self::Content(content) => content.serialize(serializer),
+        }
+    }
+}
+
+/// Serializes the other entries of a tagged variant with those split off from them put
+/// back at their positions, or after them if there are no longer as many other entries.
+fn serialize_entries<S, F>(
+    fields: &UnknownFields<F>,
+    mut split: Vec<(usize, &str, Entry<'_, F>)>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    F: Serialize,
+{
+    split.sort_by_key(|(position, ..)| *position);
+    let mut map = serializer.serialize_map(Some(fields.len() + split.len()))?;
+    let mut split = split.into_iter().peekable();
+    let mut fields = fields.iter();
+    for i in 0.. {
+        if let Some((_, key, entry)) = split.next_if(|(position, ..)| *position <= i) {
+            map.serialize_entry(key, &entry)?;
+        } else if let Some((key, value)) = fields.next() {
+            map.serialize_entry(key, value)?;
+        } else {
+            break;
+        }
+    }
+    for (_, key, entry) in split {
+        map.serialize_entry(key, &entry)?;
+    }
+
+    map.end()
+}
@@ -0,0 +1,125 @@
+use std::{borrow::Cow, fmt};
+
+use serde::{de, de::DeserializeOwned, ser, Deserialize, Serialize};
+
+use crate::{path, Segment, Value};
+
+/// The reason captured fallback content could not be converted into another fallback type,
+/// such as a CBOR tag or a map with integer keys, neither of which JSON can represent.
+///
+/// Serialized as a map, e.g.
+/// `{"ty":"Tank","path":[{"field":"fishes"},{"index":1}],"message":"..."}`,
+/// with the path listed outermost segment first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscodeError {
+    ty: Cow<'static, str>,
+    // Held innermost segment first, as it is recorded while the error unwinds.
+    #[serde(with = "path::outermost_first")]
+    path: Vec<Segment>,
+    message: String,
+}
+impl std::error::Error for TranscodeError {}
+impl fmt::Display for TranscodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "transcoding failed: {}", self.ty)?;
+        for segment in self.path.iter().rev() {
+            write!(f, ".{segment}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl TranscodeError {
+    pub fn new(ty: &'static str, message: impl fmt::Display) -> Self {
+        Self {
+            ty: Cow::Borrowed(ty),
+            path: vec![],
+            message: message.to_string(),
+        }
+    }
+
+    /// Records that the failure occurred within the derived type `ty`, at the given segment
+    /// of its serialized form, as [`Error::extend`](crate::Error::extend) does.
+    pub fn extend(mut self, ty: &'static str, segment: impl Into<Option<Segment>>) -> Self {
+        self.ty = Cow::Borrowed(ty);
+        match segment.into() {
+            Some(segment) => self.within(segment),
+            None => self,
+        }
+    }
+
+    /// Records that the failure occurred at the given segment of a container, as
+    /// [`Error::within`](crate::Error::within) does.
+    pub fn within(mut self, segment: Segment) -> Self {
+        self.path.push(segment);
+        self
+    }
+
+    /// The name of the outermost derived type which failed to transcode, or of the type
+    /// which failed to transcode if it is not held by one.
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /// The path to the content which could not be converted, outermost segment first.
+    pub fn path(&self) -> Vec<Segment> {
+        self.path.iter().rev().cloned().collect()
+    }
+
+    /// Renders the path to the content which could not be converted as an RFC 6901 JSON
+    /// Pointer, e.g. `/fishes/1`.
+    pub fn pointer(&self) -> String {
+        path::to_pointer(&self.path)
+    }
+
+    /// Why the content could not be converted.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl ser::Error for TranscodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::new("Value", msg)
+    }
+}
+
+impl de::Error for TranscodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::new("Value", msg)
+    }
+}
+
+/// A devolved value whose captured fallback content can be converted from the fallback
+/// type `F` into `G`, giving the devolved form of the same value for `G`.
+///
+/// Implemented by the derive macro for every devolved type and each fallback type it is
+/// devolved for, and for the containers [`Devolve`](crate::Devolve) is implemented for.
+pub trait MapFallback<F, G>: Sized {
+    /// The devolved form of the same value, holding fallback content of type `G`.
+    type Mapped;
+
+    /// Converts every piece of captured fallback content with `f`, failing with the path
+    /// to the first which could not be converted.
+    fn map_fallback<M>(self, f: &mut M) -> Result<Self::Mapped, TranscodeError>
+    where
+        M: FnMut(F) -> Result<G, TranscodeError>;
+
+    /// Converts every piece of captured fallback content into `G` with [`transcode`].
+    fn transcode(self) -> Result<Self::Mapped, TranscodeError>
+    where
+        F: Serialize,
+        G: DeserializeOwned,
+    {
+        self.map_fallback(&mut transcode)
+    }
+}
+
+/// Converts fallback content into another fallback type by way of [`Value`], failing
+/// where `G` cannot represent the content, such as a CBOR tag, bytes or a map with
+/// non-string keys going to JSON.
+pub fn transcode<F: Serialize, G: DeserializeOwned>(content: F) -> Result<G, TranscodeError> {
+    Value::serialized(&content)
+        .and_then(Value::deserialized)
+        .map_err(|e| e.extend(std::any::type_name::<F>(), None::<Segment>))
+}
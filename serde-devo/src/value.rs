@@ -0,0 +1,265 @@
+use std::fmt;
+
+use serde::{
+    de::{self, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq, SerializeTupleVariant},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Fallback, TranscodeError};
+
+mod deserializer;
+mod serializer;
+
+// The names under which `ciborium` passes CBOR tags through serde.
+const CBOR_TAG: &str = "@@TAG@@";
+const CBOR_TAGGED: &str = "@@TAGGED@@";
+
+/// A self-describing value which can hold content deserialized from any self-describing
+/// format, and serialize it again into any other, without depending on either.
+///
+/// Each value is held as the type it was deserialized as, so that it is serialized again
+/// in the same form, and maps are kept in the order they were received. CBOR tags are
+/// retained as [`Value::Tag`], which is serialized as a tag again by `ciborium`.
+///
+/// Other enum variants which a format hands to serde as such, like YAML's `!Variant` tags,
+/// are held in their externally tagged form, as a map from the variant name to its content,
+/// and are serialized in that form rather than as the format's own representation. Formats
+/// which drop variant names when deserializing any value, such as RON, cannot be captured
+/// with them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    String(String),
+    Bytes(Vec<u8>),
+    None,
+    Some(Box<Value>),
+    Seq(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Tag(u64, Box<Value>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Serializes any value into a `Value`, retaining CBOR tags passed through serde by
+    /// `ciborium`.
+    pub fn serialized<T: Serialize + ?Sized>(value: &T) -> Result<Self, TranscodeError> {
+        value.serialize(serializer::ValueSerializer)
+    }
+
+    /// Deserializes this value as any other type, failing with the path to the first piece
+    /// of content which that type cannot represent.
+    pub fn deserialized<T: de::DeserializeOwned>(self) -> Result<T, TranscodeError> {
+        T::deserialize(self)
+    }
+
+    /// The value of the map entry with the given string key, if this is a map.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| k.as_str() == Some(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+impl Fallback for Value {
+    fn variant_tag(&self, tag_key: Option<&str>) -> Option<&str> {
+        match (self, tag_key) {
+            (Self::String(tag), None) => Some(tag),
+            (Self::Map(entries), None) if entries.len() == 1 => entries[0].0.as_str(),
+            (Self::Map(_), Some(key)) => self.get(key)?.as_str(),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unit => serializer.serialize_unit(),
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            Self::I8(i) => serializer.serialize_i8(*i),
+            Self::I16(i) => serializer.serialize_i16(*i),
+            Self::I32(i) => serializer.serialize_i32(*i),
+            Self::I64(i) => serializer.serialize_i64(*i),
+            Self::I128(i) => serializer.serialize_i128(*i),
+            Self::U8(u) => serializer.serialize_u8(*u),
+            Self::U16(u) => serializer.serialize_u16(*u),
+            Self::U32(u) => serializer.serialize_u32(*u),
+            Self::U64(u) => serializer.serialize_u64(*u),
+            Self::U128(u) => serializer.serialize_u128(*u),
+            Self::F32(f) => serializer.serialize_f32(*f),
+            Self::F64(f) => serializer.serialize_f64(*f),
+            Self::Char(c) => serializer.serialize_char(*c),
+            Self::String(s) => serializer.serialize_str(s),
+            Self::Bytes(b) => serializer.serialize_bytes(b),
+            Self::None => serializer.serialize_none(),
+            Self::Some(v) => serializer.serialize_some(v),
+            Self::Seq(elements) => {
+                let mut seq = serializer.serialize_seq(Some(elements.len()))?;
+                for element in elements {
+                    seq.serialize_element(element)?;
+                }
+
+                seq.end()
+            }
+            Self::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (k, v) in entries {
+                    map.serialize_entry(k, v)?;
+                }
+
+                map.end()
+            }
+            Self::Tag(tag, v) => {
+                let mut tagged = serializer.serialize_tuple_variant(CBOR_TAG, 0, CBOR_TAGGED, 2)?;
+                tagged.serialize_field(tag)?;
+                tagged.serialize_field(v)?;
+                tagged.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+macro_rules! visit_values {
+    ($($method:ident($ty:ty) => $variant:ident,)+) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<Value, E> {
+                Ok(Value::$variant(v))
+            }
+        )+
+    };
+}
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any self-describing value")
+    }
+
+    visit_values! {
+        visit_bool(bool) => Bool,
+        visit_i8(i8) => I8,
+        visit_i16(i16) => I16,
+        visit_i32(i32) => I32,
+        visit_i64(i64) => I64,
+        visit_i128(i128) => I128,
+        visit_u8(u8) => U8,
+        visit_u16(u16) => U16,
+        visit_u32(u32) => U32,
+        visit_u64(u64) => U64,
+        visit_u128(u128) => U128,
+        visit_f32(f32) => F32,
+        visit_f64(f64) => F64,
+        visit_char(char) => Char,
+        visit_string(String) => String,
+        visit_byte_buf(Vec<u8>) => Bytes,
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Value, E> {
+        Ok(Value::Bytes(v.to_owned()))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Unit)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer).map(|v| Value::Some(Box::new(v)))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+
+        Ok(Value::Seq(elements))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(Value::Map(entries))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Value, A::Error> {
+        struct TagVisitor;
+
+        impl<'de> Visitor<'de> for TagVisitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a tag and a tagged value")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+                let tag = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let value = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Ok(Value::Tag(tag, Box::new(value)))
+            }
+        }
+
+        let (name, variant) = data.variant::<String>()?;
+        if name == CBOR_TAGGED {
+            return variant.tuple_variant(2, TagVisitor);
+        }
+
+        let content = variant.newtype_variant()?;
+        Ok(Value::Map(vec![(Value::String(name), content)]))
+    }
+}
@@ -0,0 +1,353 @@
+use std::vec;
+
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+use super::{Value, CBOR_TAGGED};
+use crate::{key, Segment, TranscodeError};
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = TranscodeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TranscodeError> {
+        match self {
+            Self::Unit => visitor.visit_unit(),
+            Self::Bool(b) => visitor.visit_bool(b),
+            Self::I8(i) => visitor.visit_i8(i),
+            Self::I16(i) => visitor.visit_i16(i),
+            Self::I32(i) => visitor.visit_i32(i),
+            Self::I64(i) => visitor.visit_i64(i),
+            Self::I128(i) => visitor.visit_i128(i),
+            Self::U8(u) => visitor.visit_u8(u),
+            Self::U16(u) => visitor.visit_u16(u),
+            Self::U32(u) => visitor.visit_u32(u),
+            Self::U64(u) => visitor.visit_u64(u),
+            Self::U128(u) => visitor.visit_u128(u),
+            Self::F32(f) => visitor.visit_f32(f),
+            Self::F64(f) => visitor.visit_f64(f),
+            Self::Char(c) => visitor.visit_char(c),
+            Self::String(s) => visitor.visit_string(s),
+            Self::Bytes(b) => visitor.visit_byte_buf(b),
+            Self::None => visitor.visit_none(),
+            Self::Some(v) => visitor.visit_some(*v),
+            Self::Seq(elements) => visit_seq(elements, visitor),
+            Self::Map(entries) => visit_map(entries, visitor),
+            Self::Tag(tag, value) => {
+                let mut entered = false;
+                let access = TagAccess {
+                    tag,
+                    value: *value,
+                    entered: &mut entered,
+                };
+                // A visitor which does not accept enums rejects the tag without looking at
+                // its content, so the failure is attributed to the tag itself.
+                visitor.visit_enum(access).map_err(|e| match entered {
+                    true => e,
+                    false => de::Error::custom(format_args!(
+                        "CBOR tag {tag} cannot be represented: {}",
+                        e.message()
+                    )),
+                })
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TranscodeError> {
+        match self {
+            Self::None | Self::Unit => visitor.visit_none(),
+            Self::Some(v) => visitor.visit_some(*v),
+            v => visitor.visit_some(v),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TranscodeError> {
+        match self {
+            Self::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Self::Map(entries) if entries.len() == 1 => {
+                let (variant, value) = entries.into_iter().next().expect("a single entry");
+                visitor.visit_enum(VariantDeserializer { variant, value })
+            }
+            v => v.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, TranscodeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, TranscodeError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn visit_seq<'de, V: Visitor<'de>>(
+    elements: Vec<Value>,
+    visitor: V,
+) -> Result<V::Value, TranscodeError> {
+    let len = elements.len();
+    let mut seq = SeqDeserializer {
+        elements: elements.into_iter(),
+        index: 0,
+    };
+    let value = visitor.visit_seq(&mut seq)?;
+    match seq.elements.len() {
+        0 => Ok(value),
+        _ => Err(de::Error::invalid_length(
+            len,
+            &"fewer elements in sequence",
+        )),
+    }
+}
+
+fn visit_map<'de, V: Visitor<'de>>(
+    entries: Vec<(Value, Value)>,
+    visitor: V,
+) -> Result<V::Value, TranscodeError> {
+    let len = entries.len();
+    let mut map = MapDeserializer {
+        entries: entries.into_iter(),
+        value: None,
+    };
+    let value = visitor.visit_map(&mut map)?;
+    match map.entries.len() {
+        0 => Ok(value),
+        _ => Err(de::Error::invalid_length(len, &"fewer entries in map")),
+    }
+}
+
+struct SeqDeserializer {
+    elements: vec::IntoIter<Value>,
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = TranscodeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, TranscodeError> {
+        let Some(element) = self.elements.next() else {
+            return Ok(None);
+        };
+
+        let index = self.index;
+        self.index += 1;
+        seed.deserialize(element)
+            .map(Some)
+            .map_err(|e| e.within(Segment::Index(index)))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+struct MapDeserializer {
+    entries: vec::IntoIter<(Value, Value)>,
+    value: Option<(String, Value)>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = TranscodeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, TranscodeError> {
+        let Some((k, v)) = self.entries.next() else {
+            return Ok(None);
+        };
+
+        let rendered = key::to_string(&k);
+        self.value = Some((rendered.clone(), v));
+        seed.deserialize(k)
+            .map(Some)
+            .map_err(|e| e.within(Segment::Key(rendered)))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, TranscodeError> {
+        let (rendered, v) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("a map value was requested before its key"))?;
+        seed.deserialize(v)
+            .map_err(|e| e.within(Segment::Key(rendered)))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// The variant of an enum held as a single entry map, keyed by the variant name.
+struct VariantDeserializer {
+    variant: Value,
+    value: Value,
+}
+
+impl<'de> EnumAccess<'de> for VariantDeserializer {
+    type Error = TranscodeError;
+    type Variant = Value;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Value), TranscodeError> {
+        let variant = seed.deserialize(self.variant)?;
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Value {
+    type Error = TranscodeError;
+
+    fn unit_variant(self) -> Result<(), TranscodeError> {
+        match self {
+            Self::Unit | Self::None => Ok(()),
+            v => de::Deserialize::deserialize(v),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, TranscodeError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, TranscodeError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TranscodeError> {
+        self.deserialize_any(visitor)
+    }
+}
+
+/// A CBOR tag, offered as the tuple variant under which `ciborium` passes tags through
+/// serde.
+struct TagAccess<'a> {
+    tag: u64,
+    value: Value,
+    entered: &'a mut bool,
+}
+
+impl<'de> EnumAccess<'de> for TagAccess<'_> {
+    type Error = TranscodeError;
+    type Variant = TagContent;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, TagContent), TranscodeError> {
+        *self.entered = true;
+        let variant = seed.deserialize(Value::String(CBOR_TAGGED.to_owned()))?;
+        Ok((
+            variant,
+            TagContent {
+                tag: Some(self.tag),
+                value: Some(self.value),
+            },
+        ))
+    }
+}
+
+struct TagContent {
+    tag: Option<u64>,
+    value: Option<Value>,
+}
+
+impl<'de> VariantAccess<'de> for TagContent {
+    type Error = TranscodeError;
+
+    fn unit_variant(self) -> Result<(), TranscodeError> {
+        Err(de::Error::invalid_type(
+            de::Unexpected::TupleVariant,
+            &"unit variant",
+        ))
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        _: T,
+    ) -> Result<T::Value, TranscodeError> {
+        Err(de::Error::invalid_type(
+            de::Unexpected::TupleVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        mut self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, TranscodeError> {
+        visitor.visit_seq(&mut self)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, TranscodeError> {
+        Err(de::Error::invalid_type(
+            de::Unexpected::TupleVariant,
+            &"struct variant",
+        ))
+    }
+}
+
+impl<'de> SeqAccess<'de> for TagContent {
+    type Error = TranscodeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, TranscodeError> {
+        match (self.tag.take(), self.value.take()) {
+            (Some(tag), value) => {
+                self.value = value;
+                seed.deserialize(Value::U64(tag)).map(Some)
+            }
+            (None, Some(value)) => seed.deserialize(value).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.tag.is_some() as usize + self.value.is_some() as usize)
+    }
+}